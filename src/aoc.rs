@@ -0,0 +1,373 @@
+use crate::io::{aoc_input_path_in, aoc_root, Io};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    io::Cursor,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+/// The I/O handler every registered part receives. It reads from the puzzle input which was
+/// loaded into memory before the timer started, so the same solutions can also be run on
+/// examples (see [`input`]). Everything written to it is kept, see [`Io::take_output`].
+pub type Input = Io<Cursor<String>, Vec<u8>>;
+
+/// Creates an [`Input`] which reads from the given text.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::aoc::*;
+///
+/// let mut io = input("1 2 3");
+/// let sum = io.nums::<u32>().iter().sum::<u32>();
+/// io.writeln(sum);
+/// assert_eq!(io.take_output(), "6\n");
+/// ```
+pub fn input(text: &str) -> Input {
+    Io::with_reader_and_writer(Cursor::new(text.to_string()), vec![])
+}
+
+type Part = Box<dyn Fn(&mut Input) -> String>;
+
+/// Runner for Advent of Code solutions. You register the parts of each day and it runs the
+/// selected days on their inputs (see [`Io::aoc`]), prints the answers with the time they took
+/// and compares them against the expected answers in `<root>/answers/<year>.txt`.
+///
+/// The answers file contains one answer per line in the form `<day> <part> <answer>`, lines
+/// starting with `#` are ignored:
+///
+/// ```text
+/// # day part answer
+/// 5 1 35
+/// 5 2 46
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use crate::cp_rs::aoc::*;
+/// use crate::cp_rs::io::*;
+/// use std::io::{Read, Write};
+///
+/// fn part1<R: Read, W: Write>(io: &mut Io<R, W>) -> usize {
+///     io.nums::<usize>().iter().sum()
+/// }
+///
+/// fn part2(io: &mut Input) -> usize {
+///     io.nums::<usize>().iter().product()
+/// }
+///
+/// Runner::new(2023).day(1, part1, part2).run();
+/// ```
+pub struct Runner {
+    year: u32,
+    root: PathBuf,
+    parts: BTreeMap<(u32, u32), Part>,
+}
+
+/// The result of running a single part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub time: Duration,
+    /// The answer found in the answers file, if there is one.
+    pub expected: Option<String>,
+}
+
+impl Outcome {
+    /// Returns false if the answer differs from the expected one. Unknown answers count as
+    /// correct.
+    pub fn is_correct(&self) -> bool {
+        match &self.expected {
+            Some(e) => *e == self.answer,
+            None => true,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {:02} part {}: {} ({:.3?})",
+            self.day, self.part, self.answer, self.time
+        )?;
+        match &self.expected {
+            Some(e) if *e == self.answer => write!(f, " ok"),
+            Some(e) => write!(f, " WRONG, expected {}", e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Runner {
+    /// Creates a new runner for the given year which uses [`aoc_root`] as its root directory.
+    pub fn new(year: u32) -> Runner {
+        Runner {
+            year,
+            root: aoc_root(),
+            parts: BTreeMap::new(),
+        }
+    }
+    /// Use another root directory containing the `inputs` and `answers` folders.
+    pub fn root<P: Into<PathBuf>>(mut self, root: P) -> Runner {
+        self.root = root.into();
+        self
+    }
+    /// Registers a single part of a day. The function can return anything printable, if it
+    /// returns an empty answer (e.g. `""`) the trimmed output it wrote is used instead.
+    pub fn part<F, A>(mut self, day: u32, part: u32, f: F) -> Runner
+    where
+        F: Fn(&mut Input) -> A + 'static,
+        A: Display,
+    {
+        self.parts
+            .insert((day, part), Box::new(move |io| f(io).to_string()));
+        self
+    }
+    /// Registers both parts of a day.
+    pub fn day<F1, A1, F2, A2>(self, day: u32, part1: F1, part2: F2) -> Runner
+    where
+        F1: Fn(&mut Input) -> A1 + 'static,
+        A1: Display,
+        F2: Fn(&mut Input) -> A2 + 'static,
+        A2: Display,
+    {
+        self.part(day, 1, part1).part(day, 2, part2)
+    }
+    /// Returns the path of the answers file: `<root>/answers/<year>.txt`.
+    pub fn answers_path(&self) -> PathBuf {
+        self.root.join("answers").join(format!("{}.txt", self.year))
+    }
+    /// Reads the expected answers, a missing file means that no answers are known yet.
+    pub fn answers(&self) -> BTreeMap<(u32, u32), String> {
+        let content = fs::read_to_string(self.answers_path()).unwrap_or_default();
+        let mut answers = BTreeMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut tokens = line.splitn(3, char::is_whitespace);
+            let day = tokens.next().and_then(|d| d.parse().ok());
+            let part = tokens.next().and_then(|p| p.parse().ok());
+            let answer = tokens.next().map(|a| a.trim().to_string());
+            match (day, part, answer) {
+                (Some(day), Some(part), Some(answer)) => {
+                    answers.insert((day, part), answer);
+                }
+                _ => panic!(
+                    "invalid line in {}: {:?}, expected `<day> <part> <answer>`",
+                    self.answers_path().display(),
+                    line
+                ),
+            }
+        }
+        answers
+    }
+    /// Runs all registered parts of the given days (all days if `days` is empty) and returns
+    /// their outcomes without printing anything.
+    pub fn run_days(&self, days: &[u32]) -> Vec<Outcome> {
+        let answers = self.answers();
+        let mut inputs = BTreeMap::new();
+        let mut outcomes = vec![];
+        for (&(day, part), f) in &self.parts {
+            if !days.is_empty() && !days.contains(&day) {
+                continue;
+            }
+            let input = inputs.entry(day).or_insert_with(|| {
+                Io::open_aoc_file(&aoc_input_path_in(&self.root, self.year, day)).read_all()
+            });
+            let mut io = self::input(input);
+            let start = Instant::now();
+            let mut answer = f(&mut io);
+            let time = start.elapsed();
+            if answer.is_empty() {
+                answer = io.take_output().trim().to_string();
+            }
+            outcomes.push(Outcome {
+                day,
+                part,
+                answer,
+                time,
+                expected: answers.get(&(day, part)).cloned(),
+            });
+        }
+        outcomes
+    }
+    /// Runs the days given as command line arguments (all days if there are none) and prints
+    /// the results. With the argument `--record` all answers which are not in the answers file
+    /// yet are appended to it. The process exits with code 1 if any answer is wrong.
+    pub fn run(&self) {
+        let mut record = false;
+        let mut days = vec![];
+        for arg in std::env::args().skip(1) {
+            if arg == "--record" {
+                record = true;
+            } else {
+                days.push(
+                    arg.trim_start_matches("day")
+                        .parse()
+                        .unwrap_or_else(|_| panic!("invalid day {:?}", arg)),
+                );
+            }
+        }
+        let outcomes = self.run_days(&days);
+        let mut total = Duration::ZERO;
+        for outcome in &outcomes {
+            println!("{}", outcome);
+            total += outcome.time;
+        }
+        println!("total: {:.3?}", total);
+        if record {
+            self.record(&outcomes);
+        }
+        if outcomes.iter().any(|o| !o.is_correct()) {
+            std::process::exit(1);
+        }
+    }
+    /// Appends the answers of all outcomes without an expected answer to the answers file.
+    pub fn record(&self, outcomes: &[Outcome]) {
+        let new = outcomes
+            .iter()
+            .filter(|o| o.expected.is_none())
+            .map(|o| format!("{} {} {}\n", o.day, o.part, o.answer))
+            .collect::<String>();
+        if new.is_empty() {
+            return;
+        }
+        let path = self.answers_path();
+        let mut content = fs::read_to_string(&path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&new);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).expect("could not create the answers directory");
+        }
+        fs::write(&path, content).expect("could not write the answers file");
+    }
+}
+
+#[doc(hidden)]
+pub fn day_from_name(name: &str) -> u32 {
+    name.trim_start_matches(|c: char| !c.is_ascii_digit())
+        .parse()
+        .unwrap_or_else(|_| panic!("{:?} does not end with a day number", name))
+}
+
+/// This macro creates a `main` function which runs the given day modules with a [`Runner`].
+/// Every module needs a `part1` and a `part2` function taking `&mut Input` (or a generic `Io`)
+/// and its name has to end with the day number.
+///
+/// # Example
+///
+/// ```no_run
+/// mod day01 {
+///     use cp_rs::aoc::Input;
+///     pub fn part1(io: &mut Input) -> usize {
+///         io.nums::<usize>().iter().sum()
+///     }
+///     pub fn part2(io: &mut Input) -> usize {
+///         io.nums::<usize>().iter().max().copied().unwrap_or(0)
+///     }
+/// }
+///
+/// cp_rs::aoc_main!(2023; day01);
+/// ```
+#[macro_export]
+macro_rules! aoc_main {
+    ($year:expr; $($day:ident),* $(,)?) => {
+        fn main() {
+            $crate::aoc::Runner::new($year)
+                $(.day($crate::aoc::day_from_name(stringify!($day)), $day::part1, $day::part2))*
+                .run();
+        }
+    };
+}
+
+#[doc(hidden)]
+pub use paste::paste as __paste;
+
+/// This macro generates a `#[test]` which runs a part on an example and compares the returned
+/// answer with the expected one. For parts which write their answer instead, put `writes` before
+/// the expected answer, then the trimmed output is compared and the return value is ignored. The
+/// test is called `<day>_<part>_example`, you can pass another suffix as last argument if a part
+/// has several examples. A single leading newline of the example is removed, so you can start it
+/// on its own line.
+///
+/// # Example
+///
+/// ```
+/// mod day05 {
+///     use cp_rs::aoc::Input;
+///     pub fn part1(io: &mut Input) -> usize {
+///         io.nums::<usize>().iter().sum()
+///     }
+///     pub fn part2(io: &mut Input) {
+///         let max = io.nums::<usize>().into_iter().max().unwrap();
+///         io.writeln(max);
+///     }
+/// }
+///
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///     cp_rs::aoc_test!(day05, part1, "
+/// 1 2
+/// 3 4", 10);
+///     cp_rs::aoc_test!(day05, part1, "5", 5, second);
+///     cp_rs::aoc_test!(day05, part2, "1 7 3", writes 7);
+/// }
+/// #
+/// # let mut io = cp_rs::aoc::input("1 7 3");
+/// # day05::part2(&mut io);
+/// # assert_eq!(io.take_output(), "7\n");
+/// ```
+#[macro_export]
+macro_rules! aoc_test {
+    ($day:ident, $part:ident, $input:expr, writes $expected:expr $(,)?) => {
+        $crate::aoc_test!($day, $part, $input, writes $expected, example);
+    };
+    ($day:ident, $part:ident, $input:expr, writes $expected:expr, $name:ident $(,)?) => {
+        $crate::aoc::__paste! {
+            #[test]
+            fn [<$day _ $part _ $name>]() {
+                let input: &str = $input;
+                let mut io = $crate::aoc::input(input.strip_prefix('\n').unwrap_or(input));
+                let _ = $day::$part(&mut io);
+                assert_eq!(
+                    io.take_output().trim(),
+                    $expected.to_string(),
+                    "wrong written answer for {}::{}",
+                    stringify!($day),
+                    stringify!($part)
+                );
+            }
+        }
+    };
+    ($day:ident, $part:ident, $input:expr, $expected:expr $(,)?) => {
+        $crate::aoc_test!($day, $part, $input, $expected, example);
+    };
+    ($day:ident, $part:ident, $input:expr, $expected:expr, $name:ident $(,)?) => {
+        $crate::aoc::__paste! {
+            #[test]
+            fn [<$day _ $part _ $name>]() {
+                let input: &str = $input;
+                let mut io = $crate::aoc::input(input.strip_prefix('\n').unwrap_or(input));
+                let answer = $day::$part(&mut io);
+                assert_eq!(
+                    answer.to_string(),
+                    $expected.to_string(),
+                    "wrong answer for {}::{}",
+                    stringify!($day),
+                    stringify!($part)
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,1163 @@
+//! Bundles a solution which uses cp-rs into a single file which can be submitted to online
+//! judges. The used modules of the library are inlined as `mod cp_rs { ... }` without their tests
+//! and doc comments.
+//!
+//! Usage: `cp-rs-bundle <solution.rs> [-o <output.rs>]`
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs, process,
+};
+
+/// Sources of all modules of the library, nested file modules are written as `parent/child`.
+const MODULES: &[(&str, &str)] = &[
+    ("aoc", include_str!("../aoc.rs")),
+    ("geometry", include_str!("../geometry.rs")),
+    ("geometry/angle", include_str!("../geometry/angle.rs")),
+    ("geometry/circle", include_str!("../geometry/circle.rs")),
+    ("geometry/clip", include_str!("../geometry/clip.rs")),
+    ("geometry/delaunay", include_str!("../geometry/delaunay.rs")),
+    ("geometry/dir", include_str!("../geometry/dir.rs")),
+    ("geometry/grid", include_str!("../geometry/grid.rs")),
+    ("geometry/hex", include_str!("../geometry/hex.rs")),
+    ("geometry/hull", include_str!("../geometry/hull.rs")),
+    ("geometry/line", include_str!("../geometry/line.rs")),
+    ("geometry/line3d", include_str!("../geometry/line3d.rs")),
+    (
+        "geometry/manhattan",
+        include_str!("../geometry/manhattan.rs"),
+    ),
+    ("geometry/nearest", include_str!("../geometry/nearest.rs")),
+    ("geometry/point3d", include_str!("../geometry/point3d.rs")),
+    ("geometry/pointn", include_str!("../geometry/pointn.rs")),
+    ("geometry/polygon", include_str!("../geometry/polygon.rs")),
+    ("geometry/raster", include_str!("../geometry/raster.rs")),
+    ("geometry/rect", include_str!("../geometry/rect.rs")),
+    ("geometry/rotation", include_str!("../geometry/rotation.rs")),
+    ("geometry/sparse", include_str!("../geometry/sparse.rs")),
+    ("geometry/sweep", include_str!("../geometry/sweep.rs")),
+    ("io", include_str!("../io.rs")),
+    ("num", include_str!("../num.rs")),
+    ("utils", include_str!("../utils.rs")),
+];
+
+/// The manifest of the library, used to find out which crates are not available on judges.
+const MANIFEST: &str = include_str!("../../Cargo.toml");
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (input, output) = match args.as_slice() {
+        [input] => (input, None),
+        [input, flag, output] if flag == "-o" => (input, Some(output)),
+        _ => {
+            eprintln!("usage: cp-rs-bundle <solution.rs> [-o <output.rs>]");
+            process::exit(2);
+        }
+    };
+    let solution = fs::read_to_string(input)
+        .unwrap_or_else(|err| fail(&format!("could not read {}: {}", input, err)));
+    let bundled = bundle(&solution).unwrap_or_else(|err| fail(&err));
+    match output {
+        Some(output) => fs::write(output, bundled)
+            .unwrap_or_else(|err| fail(&format!("could not write {}: {}", output, err))),
+        None => print!("{}", bundled),
+    }
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("error: {}", msg);
+    process::exit(1);
+}
+
+/// Returns the crates in the `[dependencies]` table of the manifest.
+fn external_crates() -> BTreeSet<String> {
+    manifest_table("dependencies")
+        .into_iter()
+        .map(|(name, _)| name.replace('-', "_"))
+        .collect()
+}
+
+/// Maps every cargo feature to the crate it enables, optional dependencies are features as well.
+fn feature_crates() -> BTreeMap<String, String> {
+    let mut features = external_crates()
+        .into_iter()
+        .map(|c| (c.clone(), c))
+        .collect::<BTreeMap<_, _>>();
+    for (name, value) in manifest_table("features") {
+        let krate = value
+            .split('"')
+            .find_map(|v| v.strip_prefix("dep:"))
+            .map(|v| v.replace('-', "_"));
+        if let Some(krate) = krate {
+            features.insert(name, krate);
+        }
+    }
+    features
+}
+
+/// The `key = value` lines of a table in the manifest.
+fn manifest_table(table: &str) -> Vec<(String, String)> {
+    let header = format!("[{}]", table);
+    let mut entries = vec![];
+    let mut inside = false;
+    for line in MANIFEST.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            inside = line == header;
+        } else if inside && !line.starts_with('#') {
+            if let Some((key, value)) = line.split_once('=') {
+                entries.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+    }
+    entries
+}
+
+/// Evaluates the predicate of a `cfg` attribute (without whitespace) for a build without any
+/// features, as the judge compiles the file on its own. Returns None for other predicates.
+fn eval_cfg(pred: &str) -> Option<bool> {
+    if pred.starts_with("feature=") {
+        return Some(false);
+    }
+    if let Some(inner) = pred.strip_prefix("not(").and_then(|p| p.strip_suffix(')')) {
+        return eval_cfg(inner).map(|b| !b);
+    }
+    let (inner, all) = match pred.strip_prefix("all(") {
+        Some(inner) => (inner, true),
+        None => (pred.strip_prefix("any(")?, false),
+    };
+    let inner = inner.strip_suffix(')')?;
+    let mut values = vec![];
+    let (mut depth, mut last) = (0, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                values.push(eval_cfg(&inner[last..i]));
+                last = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if last < inner.len() {
+        values.push(eval_cfg(&inner[last..]));
+    }
+    if values.contains(&Some(!all)) {
+        Some(!all)
+    } else if values.iter().all(|v| *v == Some(all)) {
+        Some(all)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Ident,
+    Lifetime,
+    Literal,
+    Punct,
+    Doc,
+    Comment,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: Kind,
+    start: usize,
+    end: usize,
+}
+
+/// A small Rust lexer which is just precise enough to find items, comments and string literals.
+/// `::` is a single token, all other punctuation is split into single characters.
+fn lex(src: &str) -> Result<Vec<Token>, String> {
+    let bytes = src.as_bytes();
+    let ident_char = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80;
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let b = bytes[i];
+        let at = |j: usize| bytes.get(j).copied().unwrap_or(0);
+        let kind = if b.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if b == b'/' && at(i + 1) == b'/' {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            let text = &src[start..i];
+            if (text.starts_with("///") && !text.starts_with("////")) || text.starts_with("//!") {
+                Kind::Doc
+            } else {
+                Kind::Comment
+            }
+        } else if b == b'/' && at(i + 1) == b'*' {
+            let mut depth = 0;
+            loop {
+                if i >= bytes.len() {
+                    return Err("unterminated block comment".to_string());
+                } else if bytes[i] == b'/' && at(i + 1) == b'*' {
+                    depth += 1;
+                    i += 2;
+                } else if bytes[i] == b'*' && at(i + 1) == b'/' {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            let text = &src[start..i];
+            if (text.starts_with("/**") && !text.starts_with("/***") && text != "/**/")
+                || text.starts_with("/*!")
+            {
+                Kind::Doc
+            } else {
+                Kind::Comment
+            }
+        } else if b == b'"' {
+            i = skip_string(bytes, i)?;
+            Kind::Literal
+        } else if b == b'\'' {
+            if at(i + 1) == b'\\' {
+                i += 3;
+                while i < bytes.len() && bytes[i] != b'\'' {
+                    i += 1;
+                }
+                i += 1;
+                Kind::Literal
+            } else {
+                let len = src[i + 1..].chars().next().map_or(1, char::len_utf8);
+                if at(i + 1 + len) == b'\'' {
+                    i += len + 2;
+                    Kind::Literal
+                } else {
+                    i += 1;
+                    while i < bytes.len() && ident_char(bytes[i]) {
+                        i += 1;
+                    }
+                    Kind::Lifetime
+                }
+            }
+        } else if b.is_ascii_digit() {
+            while i < bytes.len()
+                && (ident_char(bytes[i]) || (bytes[i] == b'.' && at(i + 1).is_ascii_digit()))
+            {
+                i += 1;
+            }
+            Kind::Literal
+        } else if ident_char(b) {
+            let rest = &bytes[i..];
+            let (prefix, raw) = if rest.starts_with(b"br") || rest.starts_with(b"cr") {
+                (2, true)
+            } else if rest.starts_with(b"r") {
+                (1, true)
+            } else if rest.starts_with(b"b") || rest.starts_with(b"c") {
+                (1, false)
+            } else {
+                (0, false)
+            };
+            let mut hashes = 0;
+            while raw && at(i + prefix + hashes) == b'#' {
+                hashes += 1;
+            }
+            if raw && at(i + prefix + hashes) == b'"' {
+                let closing = format!("\"{}", "#".repeat(hashes));
+                let body = i + prefix + hashes + 1;
+                match src[body..].find(&closing) {
+                    Some(end) => i = body + end + closing.len(),
+                    None => return Err("unterminated raw string".to_string()),
+                }
+                Kind::Literal
+            } else if prefix == 1 && !raw && at(i + 1) == b'"' {
+                i = skip_string(bytes, i + 1)?;
+                Kind::Literal
+            } else if prefix == 1 && !raw && b == b'b' && at(i + 1) == b'\'' {
+                i += 2;
+                while i < bytes.len() && bytes[i] != b'\'' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+                Kind::Literal
+            } else {
+                if raw && hashes == 1 && prefix == 1 {
+                    i += 2;
+                }
+                while i < bytes.len() && ident_char(bytes[i]) {
+                    i += 1;
+                }
+                Kind::Ident
+            }
+        } else if b == b':' && at(i + 1) == b':' {
+            i += 2;
+            Kind::Punct
+        } else {
+            i += src[i..].chars().next().map_or(1, char::len_utf8);
+            Kind::Punct
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: i,
+        });
+    }
+    Ok(tokens)
+}
+
+/// Skips a string literal starting at the quote at position i.
+fn skip_string(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+    i += 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Ok(i + 1),
+            _ => i += 1,
+        }
+    }
+    Err("unterminated string literal".to_string())
+}
+
+/// An item (or an associated item inside an impl, trait or inline module).
+#[derive(Debug, Clone)]
+struct Item {
+    /// Byte range of the item, including its attributes.
+    start: usize,
+    end: usize,
+    /// Contents of the attributes without whitespace, e.g. `cfg(test)`, and their byte ranges.
+    attrs: Vec<String>,
+    attr_spans: Vec<(usize, usize)>,
+    keyword: String,
+    /// The names this item makes available, e.g. the function name or the leaves of a use tree.
+    names: Vec<String>,
+    /// Byte offset of the opening brace of the body, if there is one.
+    body_start: Option<usize>,
+    /// Set for `mod name;`: the byte range of the `;`.
+    file_mod: Option<(usize, usize)>,
+    trait_impl: bool,
+    children: Vec<Item>,
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    code: Vec<Token>,
+    matching: Vec<usize>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str, tokens: &[Token]) -> Result<Parser<'a>, String> {
+        let code = tokens
+            .iter()
+            .copied()
+            .filter(|t| t.kind != Kind::Doc && t.kind != Kind::Comment)
+            .collect::<Vec<_>>();
+        let mut matching = vec![usize::MAX; code.len()];
+        let mut stack = vec![];
+        for (i, t) in code.iter().enumerate() {
+            if t.kind != Kind::Punct {
+                continue;
+            }
+            match &src[t.start..t.end] {
+                "(" | "[" | "{" => stack.push(i),
+                ")" | "]" | "}" => {
+                    let open = stack.pop().ok_or("unbalanced brackets")?;
+                    matching[open] = i;
+                    matching[i] = open;
+                }
+                _ => {}
+            }
+        }
+        if !stack.is_empty() {
+            return Err("unbalanced brackets".to_string());
+        }
+        Ok(Parser {
+            src,
+            code,
+            matching,
+        })
+    }
+    fn text(&self, i: usize) -> &'a str {
+        self.code.get(i).map_or("", |t| &self.src[t.start..t.end])
+    }
+    /// Byte ranges of code behind a `cfg` attribute which is false without features. This also
+    /// finds gated code inside macro bodies, where no items are parsed. An attribute covers the
+    /// code up to the next `;` or the end of the next block.
+    fn disabled(&self) -> Vec<(usize, usize)> {
+        let mut ranges = vec![];
+        for i in 0..self.code.len() {
+            if self.text(i) != "#" || self.text(i + 1) != "[" || self.text(i + 2) != "cfg" {
+                continue;
+            }
+            let close = self.matching[i + 1];
+            let pred = (i + 4..close - 1).map(|j| self.text(j)).collect::<String>();
+            if eval_cfg(&pred) != Some(false) {
+                continue;
+            }
+            let mut j = close + 1;
+            while j < self.code.len() && !matches!(self.text(j), ";" | "{" | "}") {
+                if matches!(self.text(j), "(" | "[") {
+                    j = self.matching[j];
+                }
+                j += 1;
+            }
+            let end = match self.text(j) {
+                "{" => self.matching[j],
+                ";" => j,
+                _ => j - 1,
+            };
+            ranges.push((self.code[i].start, self.code[end].end));
+        }
+        ranges
+    }
+    fn items(&self, mut i: usize, hi: usize) -> Vec<Item> {
+        let mut items = vec![];
+        while i < hi {
+            let (item, next) = self.item(i, hi);
+            items.push(item);
+            i = next;
+        }
+        items
+    }
+    /// Parses the item starting at token i and returns it with the index of the next token.
+    fn item(&self, start: usize, hi: usize) -> (Item, usize) {
+        let mut i = start;
+        let mut attrs = vec![];
+        let mut attr_spans = vec![];
+        while self.text(i) == "#" {
+            let inner = self.text(i + 1) == "!";
+            let open = if inner { i + 2 } else { i + 1 };
+            if self.text(open) != "[" {
+                break;
+            }
+            let close = self.matching[open];
+            attrs.push((open + 1..close).map(|j| self.text(j)).collect::<String>());
+            attr_spans.push((self.code[i].start, self.code[close].end));
+            i = close + 1;
+            if inner {
+                return (
+                    self.finish(start, close, (attrs, attr_spans), "#!", vec![]),
+                    i,
+                );
+            }
+        }
+        loop {
+            match self.text(i) {
+                "pub" => {
+                    i += 1;
+                    if self.text(i) == "(" {
+                        i = self.matching[i] + 1;
+                    }
+                }
+                "unsafe" | "async" | "default" => i += 1,
+                "extern" if self.text(i + 1) != "crate" => {
+                    i += 1;
+                    if self.code.get(i).map(|t| t.kind) == Some(Kind::Literal) {
+                        i += 1;
+                    }
+                }
+                "const" if matches!(self.text(i + 1), "fn" | "unsafe" | "async" | "extern") => {
+                    i += 1
+                }
+                _ => break,
+            }
+        }
+        let keyword = self.text(i).to_string();
+        if i + 1 >= hi || keyword == ";" {
+            let end = i.min(hi - 1);
+            return (
+                self.finish(start, end, (attrs, attr_spans), &keyword, vec![]),
+                end + 1,
+            );
+        }
+        if self.text(i + 1) == "!" {
+            // macro_rules! name { ... } or an item macro like some_macro!(...);
+            let (names, group) = if keyword == "macro_rules" {
+                (vec![self.text(i + 2).to_string()], i + 3)
+            } else {
+                (vec![], i + 2)
+            };
+            let mut end = match self.matching.get(group) {
+                Some(&close) if close != usize::MAX => close,
+                _ => group,
+            };
+            if self.text(group) != "{" && self.text(end + 1) == ";" {
+                end += 1;
+            }
+            let end = end.min(hi - 1);
+            return (
+                self.finish(start, end, (attrs, attr_spans), &keyword, names),
+                end + 1,
+            );
+        }
+        if matches!(
+            keyword.as_str(),
+            "use" | "const" | "static" | "type" | "extern"
+        ) {
+            let end = self.find_semicolon(i, hi).min(hi - 1);
+            let names = match keyword.as_str() {
+                "use" => self.use_leaves(i + 1, end),
+                "extern" => vec![self.text(i + 2).to_string()],
+                _ => vec![self.text(i + 1).to_string()],
+            };
+            return (
+                self.finish(start, end, (attrs, attr_spans), &keyword, names),
+                end + 1,
+            );
+        }
+        // fn, struct, enum, union, trait, impl and mod end with `;` or with their body
+        let mut j = i + 1;
+        let mut body = None;
+        while j < hi {
+            match self.text(j) {
+                ";" => break,
+                "{" => {
+                    body = Some(j);
+                    j = self.matching[j];
+                    break;
+                }
+                "(" | "[" => j = self.matching[j] + 1,
+                _ => j += 1,
+            }
+        }
+        let end = j.min(hi - 1);
+        let names = match keyword.as_str() {
+            "impl" => vec![],
+            _ => vec![self.text(i + 1).to_string()],
+        };
+        let mut item = self.finish(start, end, (attrs, attr_spans), &keyword, names);
+        match body {
+            Some(open) => {
+                item.body_start = Some(self.code[open].start);
+                item.trait_impl = keyword == "impl" && (i..open).any(|k| self.text(k) == "for");
+                if matches!(keyword.as_str(), "impl" | "trait" | "mod") {
+                    item.children = self.items(open + 1, self.matching[open]);
+                }
+            }
+            None if keyword == "mod" => {
+                item.file_mod = Some((self.code[end].start, self.code[end].end));
+            }
+            None => {}
+        }
+        (item, end + 1)
+    }
+    fn finish(
+        &self,
+        start: usize,
+        end: usize,
+        (attrs, attr_spans): (Vec<String>, Vec<(usize, usize)>),
+        keyword: &str,
+        names: Vec<String>,
+    ) -> Item {
+        Item {
+            start: self.code[start].start,
+            end: self.code[end].end,
+            attrs,
+            attr_spans,
+            keyword: keyword.to_string(),
+            names,
+            body_start: None,
+            file_mod: None,
+            trait_impl: false,
+            children: vec![],
+        }
+    }
+    fn find_semicolon(&self, mut i: usize, hi: usize) -> usize {
+        while i < hi && self.text(i) != ";" {
+            i = match self.text(i) {
+                "(" | "[" | "{" => self.matching[i] + 1,
+                _ => i + 1,
+            };
+        }
+        i
+    }
+    /// The names a use tree brings into scope (aliases replace the original name).
+    fn use_leaves(&self, lo: usize, hi: usize) -> Vec<String> {
+        (lo..hi)
+            .filter(|&j| {
+                self.code[j].kind == Kind::Ident
+                    && matches!(self.text(j + 1), "," | "}" | ";")
+                    && self.text(j) != "self"
+            })
+            .map(|j| self.text(j).to_string())
+            .collect()
+    }
+}
+
+/// A lexed module of the library.
+struct Module {
+    path: String,
+    src: String,
+    tokens: Vec<Token>,
+    /// The tokens without comments.
+    code: Vec<Token>,
+    items: Vec<Item>,
+    /// Byte ranges of code which is disabled without features.
+    disabled: Vec<(usize, usize)>,
+}
+
+impl Module {
+    fn new(path: &str, src: &str) -> Result<Module, String> {
+        let tokens = lex(src).map_err(|err| format!("{} in module {}", err, path))?;
+        let parser = Parser::new(src, &tokens).map_err(|err| format!("{} in {}", err, path))?;
+        let items = parser.items(0, parser.code.len());
+        let disabled = parser.disabled();
+        Ok(Module {
+            path: path.to_string(),
+            src: src.to_string(),
+            code: parser.code,
+            tokens,
+            items,
+            disabled,
+        })
+    }
+    fn tok(&self, t: Token) -> &str {
+        &self.src[t.start..t.end]
+    }
+    fn is_enabled(&self, t: &Token) -> bool {
+        !self
+            .disabled
+            .iter()
+            .any(|&(s, e)| s <= t.start && t.end <= e)
+    }
+    /// Top level modules referenced with `crate::name` outside of removed items.
+    fn crate_references(&self, removed: &BTreeSet<(String, usize)>) -> BTreeSet<String> {
+        let ranges = walk(&self.items)
+            .into_iter()
+            .filter(|item| removed.contains(&(self.path.clone(), item.start)))
+            .map(|item| (item.start, item.end))
+            .collect::<Vec<_>>();
+        self.code
+            .windows(3)
+            .filter(|w| self.tok(w[0]) == "crate" && self.tok(w[1]) == "::")
+            .filter(|w| {
+                !ranges
+                    .iter()
+                    .any(|&(s, e)| s <= w[0].start && w[0].end <= e)
+            })
+            .map(|w| self.tok(w[2]).to_string())
+            .collect()
+    }
+}
+
+/// All items including the nested ones.
+fn walk(items: &[Item]) -> Vec<&Item> {
+    let mut all = vec![];
+    for item in items {
+        all.push(item);
+        all.extend(walk(&item.children));
+    }
+    all
+}
+
+/// Identifiers in the byte range which look like a use of an item: capitalized names (types,
+/// traits, constants), names followed by `(`, `!`, `::` or `<` and names after `.` or `::`.
+/// Plain lowercase names like local variables are ignored.
+fn references(src: &str, tokens: &[Token], start: usize, end: usize) -> BTreeSet<String> {
+    let code = tokens
+        .iter()
+        .filter(|t| t.kind != Kind::Doc && t.kind != Kind::Comment)
+        .filter(|t| start <= t.start && t.end <= end)
+        .collect::<Vec<_>>();
+    let text = |i: usize| code.get(i).map_or("", |t| &src[t.start..t.end]);
+    let mut refs = BTreeSet::new();
+    for (i, t) in code.iter().enumerate() {
+        if t.kind != Kind::Ident {
+            continue;
+        }
+        let name = text(i);
+        let before = if i > 0 { text(i - 1) } else { "" };
+        if name.starts_with(|c: char| c.is_ascii_uppercase())
+            || matches!(text(i + 1), "(" | "!" | "::" | "<")
+            || matches!(before, "." | "::")
+        {
+            refs.insert(name.to_string());
+        }
+    }
+    refs
+}
+
+fn is_test(item: &Item) -> bool {
+    item.attrs.iter().any(|a| a == "cfg(test)" || a == "test")
+}
+
+/// Why an item can't be bundled: the external crate it depends on.
+type Dependent = BTreeMap<(String, usize), String>;
+
+/// Finds all items which (transitively) depend on an external crate. Items which are disabled
+/// without cargo features count as depending on the crate of the feature.
+fn dependent_items(modules: &[&Module]) -> Dependent {
+    let externals = external_crates();
+    let features = feature_crates();
+    let mut dependent = Dependent::new();
+    for module in modules {
+        for item in walk(&module.items) {
+            for attr in &item.attrs {
+                let pred = match attr.strip_prefix("cfg(").and_then(|a| a.strip_suffix(')')) {
+                    Some(pred) => pred,
+                    None => continue,
+                };
+                if eval_cfg(pred) == Some(false) {
+                    let reason = pred
+                        .split("feature=\"")
+                        .skip(1)
+                        .filter_map(|f| f.split('"').next())
+                        .map(|f| features.get(f).cloned().unwrap_or_else(|| f.to_string()))
+                        .next()
+                        .unwrap_or_default();
+                    dependent.insert((module.path.clone(), item.start), reason);
+                }
+            }
+        }
+    }
+    // gated code inside an item (e.g. in a macro body) doesn't count
+    let enabled = modules
+        .iter()
+        .map(|m| {
+            m.tokens
+                .iter()
+                .copied()
+                .filter(|t| m.is_enabled(t))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    loop {
+        let names = dependent_names(modules, &dependent);
+        let mut changed = false;
+        for (m, module) in modules.iter().enumerate() {
+            for item in walk(&module.items) {
+                let key = (module.path.clone(), item.start);
+                if dependent.contains_key(&key) || is_test(item) {
+                    continue;
+                }
+                // impl blocks only depend on their header, their methods are checked separately
+                let end = if item.children.is_empty() {
+                    item.end
+                } else {
+                    item.body_start.unwrap_or(item.end)
+                };
+                let code = module
+                    .code
+                    .iter()
+                    .copied()
+                    .filter(|t| item.start <= t.start && t.end <= end && module.is_enabled(t))
+                    .collect::<Vec<_>>();
+                let mut reason = code.windows(2).find_map(|w| {
+                    let name = module.tok(w[0]);
+                    (externals.contains(name) && module.tok(w[1]) == "::").then(|| name.to_string())
+                });
+                if reason.is_none() && item.keyword == "use" {
+                    reason = code
+                        .iter()
+                        .skip_while(|t| module.tok(**t) != "use")
+                        .find(|t| t.kind == Kind::Ident && module.tok(**t) != "use")
+                        .map(|t| module.tok(*t).to_string())
+                        .filter(|name| externals.contains(name));
+                }
+                if reason.is_none() {
+                    reason = references(&module.src, &enabled[m], item.start, end)
+                        .iter()
+                        .filter(|name| !item.names.contains(name) || item.keyword == "use")
+                        .find_map(|name| names.get(name).cloned());
+                }
+                if let Some(reason) = reason {
+                    dependent.insert(key, reason);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    // a trait impl can't lose single methods, so it goes away completely
+    for module in modules {
+        for item in walk(&module.items) {
+            let key = (module.path.clone(), item.start);
+            if item.trait_impl && !dependent.contains_key(&key) {
+                let reason = item
+                    .children
+                    .iter()
+                    .find_map(|c| dependent.get(&(module.path.clone(), c.start)).cloned());
+                if let Some(reason) = reason {
+                    dependent.insert(key, reason);
+                }
+            }
+        }
+    }
+    dependent
+}
+
+/// Names which are only defined by dependent items, with the crate they depend on. Re-exports
+/// don't keep a name available, as they only refer to another definition.
+fn dependent_names(modules: &[&Module], dependent: &Dependent) -> BTreeMap<String, String> {
+    let mut names = BTreeMap::new();
+    let mut available = BTreeSet::new();
+    for module in modules {
+        for item in walk(&module.items) {
+            match dependent.get(&(module.path.clone(), item.start)) {
+                Some(reason) => {
+                    for name in &item.names {
+                        names.insert(name.clone(), reason.clone());
+                    }
+                }
+                None if item.keyword != "use" && !is_test(item) => {
+                    available.extend(item.names.iter().cloned())
+                }
+                None => {}
+            }
+        }
+    }
+    names.retain(|name, _| !available.contains(name));
+    names
+}
+
+/// Bundles the solution with all library modules it uses.
+fn bundle(solution: &str) -> Result<String, String> {
+    let modules = MODULES
+        .iter()
+        .map(|(path, src)| Module::new(path, src))
+        .collect::<Result<Vec<_>, _>>()?;
+    let by_path = modules
+        .iter()
+        .map(|m| (m.path.as_str(), m))
+        .collect::<BTreeMap<_, _>>();
+    let tokens = lex(solution).map_err(|err| format!("{} in the solution", err))?;
+    let parser =
+        Parser::new(solution, &tokens).map_err(|err| format!("{} in the solution", err))?;
+    let code = &parser.code;
+
+    let mut macros = BTreeMap::new();
+    for module in &modules {
+        for item in walk(&module.items) {
+            if item.keyword == "macro_rules" && item.attrs.iter().any(|a| a == "macro_export") {
+                macros.insert(item.names[0].clone(), module.path.clone());
+            }
+        }
+    }
+
+    // modules used by the solution: cp_rs::module, cp_rs::{a, b::c}, cp_rs::* and cp_rs::macro!
+    let mut selected = BTreeSet::new();
+    let mut edits = vec![];
+    for i in 0..code.len() {
+        if parser.text(i) != "cp_rs" {
+            continue;
+        }
+        let prev = if i > 0 { parser.text(i - 1) } else { "" };
+        let local = prev == "::" && i > 1 && matches!(parser.text(i - 2), "crate" | "self");
+        if prev == "crate" && parser.text(i + 1) == ";" {
+            // extern crate cp_rs;
+            edits.push((code[i - 2].start, code[i + 1].end, String::new()));
+            continue;
+        }
+        if parser.text(i + 1) != "::" {
+            continue;
+        }
+        let mut first = vec![];
+        if parser.text(i + 2) == "{" {
+            let mut depth = 0;
+            for j in i + 3..parser.matching[i + 2] {
+                match parser.text(j) {
+                    "{" => depth += 1,
+                    "}" => depth -= 1,
+                    _ if depth == 0 && matches!(parser.text(j - 1), "{" | ",") => {
+                        first.push(parser.text(j).to_string())
+                    }
+                    _ => {}
+                }
+            }
+        } else {
+            first.push(parser.text(i + 2).to_string());
+        }
+        let mut replacement = "crate::cp_rs";
+        for name in first {
+            if name == "*" {
+                selected.extend(
+                    modules
+                        .iter()
+                        .filter(|m| !m.path.contains('/'))
+                        .map(|m| m.path.clone()),
+                );
+            } else if let Some(module) = macros.get(&name) {
+                selected.insert(module.clone());
+                replacement = "crate";
+            } else if by_path.contains_key(name.as_str()) {
+                selected.insert(name);
+            } else if name != "self" {
+                return Err(format!("cp_rs has no module called `{}`", name));
+            }
+        }
+        if !local {
+            let start = if prev == "::" {
+                code[i - 1].start
+            } else {
+                code[i].start
+            };
+            edits.push((start, code[i].end, replacement.to_string()));
+        }
+    }
+    if selected.is_empty() {
+        return Ok(solution.to_string());
+    }
+
+    let all = modules.iter().collect::<Vec<_>>();
+    let dependent = dependent_items(&all);
+    let dependent_names = dependent_names(&all, &dependent);
+
+    // add the top level modules referenced with crate::name by the selected ones
+    let mut removed = dependent.keys().cloned().collect::<BTreeSet<_>>();
+    for module in &modules {
+        for item in walk(&module.items) {
+            if is_test(item) {
+                removed.insert((module.path.clone(), item.start));
+            }
+        }
+    }
+    let mut queue = selected.iter().cloned().collect::<Vec<_>>();
+    while let Some(path) = queue.pop() {
+        let nested = format!("{}/", path);
+        for module in modules
+            .iter()
+            .filter(|m| m.path == path || m.path.starts_with(&nested))
+        {
+            for dep in module.crate_references(&removed) {
+                if by_path.contains_key(dep.as_str()) && selected.insert(dep.clone()) {
+                    queue.push(dep);
+                }
+            }
+        }
+    }
+
+    // the solution must not use anything which got removed
+    let used = references(solution, &tokens, 0, solution.len());
+    let mut errors = vec![];
+    for module in &modules {
+        let top = module.path.split('/').next().unwrap_or_default();
+        if !selected.contains(top) {
+            continue;
+        }
+        for item in walk(&module.items) {
+            if let Some(krate) = dependent.get(&(module.path.clone(), item.start)) {
+                let names = item
+                    .names
+                    .iter()
+                    .filter(|n| used.contains(*n) && dependent_names.contains_key(*n));
+                for name in names {
+                    errors.push(format!(
+                        "`cp_rs::{}::{}` depends on the crate `{}`, which is not available on online judges",
+                        module.path.replace('/', "::"),
+                        name,
+                        krate
+                    ));
+                }
+            }
+        }
+    }
+    if !errors.is_empty() {
+        errors.dedup();
+        return Err(errors.join("\nerror: "));
+    }
+
+    let mut out = apply(solution, edits);
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(
+        "\n#[allow(dead_code, unused_imports, unused_macros, clippy::all)]\nmod cp_rs {\n",
+    );
+    for path in &selected {
+        let body = render(&by_path, path, &removed)?;
+        let module = format!("pub mod {} {{\n{}}}\n", path, indent(&body, 4));
+        out.push_str(&indent(&module, 4));
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// Renders a module without tests, doc comments and removed items. Nested file modules are
+/// inlined and `crate::` paths are rewritten to `crate::cp_rs::`.
+fn render(
+    modules: &BTreeMap<&str, &Module>,
+    path: &str,
+    removed: &BTreeSet<(String, usize)>,
+) -> Result<String, String> {
+    let module = modules
+        .get(path)
+        .ok_or_else(|| format!("missing source of module {}", path))?;
+    let mut edits = vec![];
+    for t in &module.tokens {
+        if t.kind == Kind::Doc {
+            edits.push((t.start, t.end, String::new()));
+        }
+    }
+    for &(start, end) in &module.disabled {
+        edits.push((start, end, String::new()));
+    }
+    for item in walk(&module.items) {
+        if removed.contains(&(path.to_string(), item.start)) {
+            edits.push((item.start, item.end, String::new()));
+            continue;
+        }
+        for (attr, &(start, end)) in item.attrs.iter().zip(&item.attr_spans) {
+            let pred = attr.strip_prefix("cfg(").and_then(|a| a.strip_suffix(')'));
+            if pred.and_then(eval_cfg) == Some(true) {
+                edits.push((start, end, String::new()));
+            }
+        }
+        if let Some((start, end)) = item.file_mod {
+            let child = format!("{}/{}", path, item.names[0]);
+            let body = render(modules, &child, removed)?;
+            edits.push((start, end, format!(" {{\n{}}}", indent(&body, 4))));
+        }
+    }
+    for w in module.code.windows(2) {
+        if module.tok(w[0]) == "crate" && module.tok(w[1]) == "::" {
+            edits.push((w[1].start, w[1].end, "::cp_rs::".to_string()));
+        }
+    }
+    Ok(tidy(&apply(&module.src, edits)))
+}
+
+/// Applies (start, end, replacement) edits, edits inside an earlier edit are dropped.
+fn apply(src: &str, mut edits: Vec<(usize, usize, String)>) -> String {
+    edits.sort_by_key(|&(start, end, _)| (start, std::cmp::Reverse(end)));
+    let mut out = String::new();
+    let mut pos = 0;
+    for (start, end, replacement) in edits {
+        if start < pos {
+            continue;
+        }
+        out.push_str(&src[pos..start]);
+        out.push_str(&replacement);
+        pos = end;
+    }
+    out.push_str(&src[pos..]);
+    out
+}
+
+/// Removes all lines which only contain whitespace, unless they are part of a string literal.
+fn tidy(src: &str) -> String {
+    let literals = literal_ranges(src);
+    let mut out = String::new();
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let in_literal = literals.iter().any(|&(s, e)| s < offset && offset < e);
+        if in_literal || !line.trim().is_empty() {
+            out.push_str(line);
+        }
+        offset += line.len();
+    }
+    out
+}
+
+/// Indents every line which doesn't start inside a string literal.
+fn indent(src: &str, n: usize) -> String {
+    let literals = literal_ranges(src);
+    let mut out = String::new();
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let in_literal = literals.iter().any(|&(s, e)| s < offset && offset < e);
+        if !in_literal && !line.trim().is_empty() {
+            out.push_str(&" ".repeat(n));
+        }
+        out.push_str(line);
+        offset += line.len();
+    }
+    out
+}
+
+fn literal_ranges(src: &str) -> Vec<(usize, usize)> {
+    lex(src)
+        .unwrap_or_default()
+        .iter()
+        .filter(|t| t.kind == Kind::Literal || t.kind == Kind::Comment)
+        .map(|t| (t.start, t.end))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_modules_are_embedded() {
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut stack = vec![src.clone()];
+        let mut files = BTreeSet::new();
+        while let Some(dir) = stack.pop() {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    if !path.ends_with("bin") {
+                        stack.push(path);
+                    }
+                } else if path.extension().is_some_and(|e| e == "rs") && !path.ends_with("lib.rs") {
+                    let rel = path.strip_prefix(&src).unwrap().with_extension("");
+                    files.insert(rel.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        let embedded = MODULES.iter().map(|(p, _)| p.to_string()).collect();
+        assert_eq!(files, embedded);
+    }
+
+    #[test]
+    fn lexes_literals() {
+        let src = r###"let a = r#"x "} y"#; let b = '}'; let c: &'a str = "\"{"; // }"###;
+        let tokens = lex(src).unwrap();
+        assert!(Parser::new(src, &tokens).is_ok());
+        assert_eq!(tokens.iter().filter(|t| t.kind == Kind::Literal).count(), 3);
+        assert_eq!(
+            tokens.iter().filter(|t| t.kind == Kind::Lifetime).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn bundles_io() {
+        let solution = "use cp_rs::io::*;\n\nfn main() {\n    let mut io = Io::new();\n    let n: usize = io.read();\n    io.writeln(n);\n}\n";
+        let bundled = bundle(solution).unwrap();
+        assert!(bundled.starts_with("use crate::cp_rs::io::*;"));
+        assert!(bundled.contains("    pub mod io {\n"));
+        assert!(!bundled.contains("regex"));
+        assert!(!bundled.contains("///"));
+        assert!(!bundled.contains("pub mod utils"));
+    }
+
+    #[test]
+    fn rejects_external_items() {
+        let err = bundle("use cp_rs::utils::Integer;\nfn main() {}").unwrap_err();
+        assert!(err.contains("`cp_rs::utils::Integer` depends on the crate `rug`"));
+        let err = bundle("use cp_rs::utils::*;\nfn main() { md5::compute(\"\"); }").unwrap_err();
+        assert!(err.contains("`md5`"));
+    }
+
+    #[test]
+    fn keeps_regex_free_fallbacks() {
+        let bundled =
+            bundle("use cp_rs::io::*;\nfn main() { let v = Io::new().nums::<i32>(); }").unwrap();
+        assert!(bundled.contains("fn numbers("));
+        assert!(!bundled.contains("feature"));
+        assert!(!bundled.contains("Regex"));
+    }
+
+    #[test]
+    fn removes_gated_code_in_macros() {
+        let bundled =
+            bundle("use cp_rs::geometry::*;\nfn main() { let _ = Point2D::new(1i64, 2).len2(); }")
+                .unwrap();
+        assert!(bundled.contains("macro_rules! impl_checked"));
+        assert!(bundled.contains("impl_signed!(i8 => i64"));
+        assert!(!bundled.contains("rug"));
+    }
+
+    #[test]
+    fn evaluates_cfg() {
+        assert_eq!(eval_cfg("feature=\"rug\""), Some(false));
+        assert_eq!(eval_cfg("not(feature=\"rug\")"), Some(true));
+        assert_eq!(eval_cfg("all(feature=\"a\",unix)"), Some(false));
+        assert_eq!(eval_cfg("any(feature=\"a\",unix)"), None);
+        assert_eq!(eval_cfg("test"), None);
+    }
+}
@@ -1,6 +1,52 @@
+use crate::num::{Num, Scalar};
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
 
+mod angle;
+mod circle;
+mod clip;
+mod delaunay;
+mod dir;
+mod grid;
+mod hex;
+mod hull;
+mod line;
+mod line3d;
+mod manhattan;
+mod nearest;
+mod point3d;
+mod pointn;
+mod polygon;
+mod raster;
+mod rect;
+mod rotation;
+mod sparse;
+mod sweep;
+pub use angle::*;
+pub use circle::*;
+pub use clip::*;
+pub use delaunay::*;
+pub use dir::*;
+pub use grid::*;
+pub use hex::*;
+pub use hull::*;
+pub use line::*;
+pub use line3d::*;
+pub use manhattan::*;
+pub use nearest::*;
+pub use point3d::*;
+pub use pointn::*;
+pub use polygon::*;
+pub use raster::*;
+pub use rect::*;
+pub use rotation::*;
+pub use sparse::*;
+pub use sweep::*;
+
+/// Tolerance used by the floating point geometry, e.g. for circles.
+pub const EPS: f64 = 1e-9;
+
 /// ```
 /// use crate::cp_rs::geometry::*;
 /// let mut p = Point2D::new(1, 2);
@@ -9,7 +55,7 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, S
 /// assert!(p.x == 4);
 /// assert!(p.y == 6);
 /// ```
-#[derive(Hash, Clone, Copy, PartialEq, Eq)]
+#[derive(Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Point2D<T> {
     pub x: T,
     pub y: T,
@@ -155,7 +201,7 @@ where
 impl<T, S> Mul<S> for Point2D<T>
 where
     T: std::ops::Mul<S, Output = T>,
-    S: Copy,
+    S: Clone,
 {
     type Output = Self;
 
@@ -173,7 +219,7 @@ where
     /// ```
     fn mul(self, rhs: S) -> Self {
         Self {
-            x: self.x * rhs,
+            x: self.x * rhs.clone(),
             y: self.y * rhs,
         }
     }
@@ -182,7 +228,7 @@ where
 impl<T, S> MulAssign<S> for Point2D<T>
 where
     T: std::ops::Mul<S, Output = T> + Clone,
-    S: Copy,
+    S: Clone,
 {
     /// You can scale a point by a scalar S, if *: (T, S) -> T exists.
     ///
@@ -198,7 +244,7 @@ where
     /// ```
     fn mul_assign(&mut self, other: S) {
         *self = Self {
-            x: self.x.clone() * other,
+            x: self.x.clone() * other.clone(),
             y: self.y.clone() * other,
         }
     }
@@ -207,7 +253,7 @@ where
 impl<T, S> Div<S> for Point2D<T>
 where
     T: std::ops::Div<S, Output = T>,
-    S: Copy,
+    S: Clone,
 {
     type Output = Self;
 
@@ -225,7 +271,7 @@ where
     /// ```
     fn div(self, rhs: S) -> Self {
         Self {
-            x: self.x / rhs,
+            x: self.x / rhs.clone(),
             y: self.y / rhs,
         }
     }
@@ -234,7 +280,7 @@ where
 impl<T, S> DivAssign<S> for Point2D<T>
 where
     T: std::ops::Div<S, Output = T> + Clone,
-    S: Copy,
+    S: Clone,
 {
     /// You can scale a point by a scalar S, if /: (T, S) -> T exists.
     ///
@@ -250,7 +296,7 @@ where
     /// ```
     fn div_assign(&mut self, other: S) {
         *self = Self {
-            x: self.x.clone() / other,
+            x: self.x.clone() / other.clone(),
             y: self.y.clone() / other,
         }
     }
@@ -259,7 +305,7 @@ where
 impl<T, S> Rem<S> for Point2D<T>
 where
     T: std::ops::Rem<S, Output = T>,
-    S: Copy,
+    S: Clone,
 {
     type Output = Self;
 
@@ -277,7 +323,7 @@ where
     /// ```
     fn rem(self, rhs: S) -> Self {
         Self {
-            x: self.x % rhs,
+            x: self.x % rhs.clone(),
             y: self.y % rhs,
         }
     }
@@ -286,7 +332,7 @@ where
 impl<T, S> RemAssign<S> for Point2D<T>
 where
     T: std::ops::Rem<S, Output = T> + Clone,
-    S: Copy,
+    S: Clone,
 {
     /// You can get the remainder of a point, e.g. if you want to limit it to a square grid.
     ///
@@ -302,7 +348,7 @@ where
     /// ```
     fn rem_assign(&mut self, other: S) {
         *self = Self {
-            x: self.x.clone() % other,
+            x: self.x.clone() % other.clone(),
             y: self.y.clone() % other,
         }
     }
@@ -310,7 +356,7 @@ where
 
 impl<T> Point2D<T>
 where
-    T: Into<f64> + Copy,
+    T: Num,
 {
     /// Calculate the length of a point (Euclidean distance to (0, 0)).
     ///
@@ -319,13 +365,24 @@ where
     /// ```
     /// use crate::cp_rs::geometry::*;
     ///
-    /// let p = Point2D::new(3, 4);
+    /// let p = Point2D::new(3i64, 4);
     /// assert!(p.len() == 5.0);
     /// ```
     pub fn len(&self) -> f64 {
-        let x: f64 = self.x.into();
-        let y: f64 = self.y.into();
-        (x * x + y * y).sqrt()
+        self.x.to_f64().hypot(self.y.to_f64())
+    }
+    /// Calculate the squared length of a point, this stays exact for integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(3u64, 4);
+    /// assert!(p.len2() == 25);
+    /// ```
+    pub fn len2(&self) -> T {
+        self.dot(self.clone())
     }
     /// Compute the dot product of two points (find out the length of the projection of one point
     /// onto the other one.
@@ -337,10 +394,134 @@ where
     ///
     /// let p = Point2D::new(1, 2);
     /// let p2 = Point2D::new(2, 1);
-    /// assert!(p.dot(p2) == 4.0);
+    /// assert!(p.dot(p2) == 4);
+    /// ```
+    pub fn dot(&self, other: Point2D<T>) -> T {
+        self.x.clone() * other.x + self.y.clone() * other.y
+    }
+    /// Compute the cross product (z component of the 3D cross product) of two points. It is
+    /// positive if other is counter-clockwise of self and twice the area of the spanned triangle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(1, 0);
+    /// let p2 = Point2D::new(0, 2);
+    /// assert!(p.cross(p2) == 2);
+    /// assert!(p2.cross(p) == -2);
+    /// ```
+    pub fn cross(&self, other: Point2D<T>) -> T {
+        self.x.clone() * other.y - self.y.clone() * other.x
+    }
+    /// Convert the coordinates into the wide type of T (see [`Num::Wide`]), e.g. to compute
+    /// products of `i64` coordinates in `i128`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(i64::MAX, 1).widen();
+    /// assert!(p.len2() == i64::MAX as i128 * i64::MAX as i128 + 1);
+    /// ```
+    pub fn widen(&self) -> Point2D<T::Wide> {
+        Point2D::new(self.x.widen(), self.y.widen())
+    }
+    /// Convert the coordinates into floats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(1u64, 2).to_f64();
+    /// assert!(p == Point2D::new(1.0, 2.0));
+    /// ```
+    pub fn to_f64(&self) -> Point2D<f64> {
+        Point2D::new(self.x.to_f64(), self.y.to_f64())
+    }
+    /// Convert the coordinates into exact rationals. Panics for NaN and infinite floats.
+    #[cfg(feature = "rug")]
+    pub fn to_rational(&self) -> Point2D<crate::utils::Rational> {
+        Point2D::new(self.x.to_rational(), self.y.to_rational())
+    }
+}
+
+/// The orientation of three points, see [`orient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// The points make a left (counter-clockwise) turn.
+    Left,
+    /// The points make a right (clockwise) turn.
+    Right,
+    /// The points lie on a line.
+    Collinear,
+}
+
+/// Compute whether the path a -> b -> c turns left (counter-clockwise), right (clockwise) or
+/// goes straight. The cross product is computed in the wide type of T, so this is exact for all
+/// integer coordinates up to 32 bits, for 64 bit coordinates below 2^62 in absolute value and for
+/// `Rational`s. For floats it is only as exact as the floats are.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let a = Point2D::new(0i64, 0);
+/// let b = Point2D::new(4, 0);
+/// assert!(orient(&a, &b, &Point2D::new(1, 1)) == Orientation::Left);
+/// assert!(orient(&a, &b, &Point2D::new(1, -1)) == Orientation::Right);
+/// assert!(orient(&a, &b, &Point2D::new(8, 0)) == Orientation::Collinear);
+///
+/// // the cross product of these points doesn't fit into an i64
+/// let big = 1i64 << 40;
+/// let a = Point2D::new(-big, -big);
+/// let b = Point2D::new(big, big + 1);
+/// assert!(orient(&a, &b, &Point2D::new(big + 1, big + 2)) == Orientation::Right);
+///
+/// // extreme values of small types don't overflow either
+/// let (lo, hi) = (i32::MIN, i32::MAX);
+/// let a = Point2D::new(lo, lo);
+/// assert!(orient(&a, &Point2D::new(hi, lo), &Point2D::new(lo, hi)) == Orientation::Left);
+/// assert!(orient(&a, &Point2D::new(hi, hi), &Point2D::new(hi - 1, hi)) == Orientation::Left);
+/// let (a, b, c) = (Point2D::new(0u8, 0), Point2D::new(255, 0), Point2D::new(0, 255));
+/// assert!(orient(&a, &b, &c) == Orientation::Left);
+/// assert!(orient(&b, &a, &c) == Orientation::Right);
+/// ```
+pub fn orient<T: Num>(a: &Point2D<T>, b: &Point2D<T>, c: &Point2D<T>) -> Orientation {
+    let a = a.widen();
+    let cross = (b.widen() - a.clone()).cross(c.widen() - a);
+    match cross.partial_cmp(&T::Wide::zero()) {
+        Some(Ordering::Greater) => Orientation::Left,
+        Some(Ordering::Less) => Orientation::Right,
+        _ => Orientation::Collinear,
+    }
+}
+
+#[cfg(feature = "rug")]
+impl Point2D<f64> {
+    /// Convert a float point into an exact rational point, so that predicates like [`orient`]
+    /// can be evaluated exactly. Returns None if a coordinate is NaN or infinite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let a = Point2D::new(0.1, 0.2).try_to_rational().unwrap();
+    /// let b = Point2D::new(0.3, 0.6).try_to_rational().unwrap();
+    /// let c = Point2D::new(0.0, 0.0).try_to_rational().unwrap();
+    /// // 0.1, 0.2, 0.3 and 0.6 are not exactly representable, so these are not collinear
+    /// assert!(orient(&a, &b, &c) != Orientation::Collinear);
     /// ```
-    pub fn dot(&self, other: Point2D<T>) -> f64 {
-        self.x.into() * other.x.into() + self.y.into() * other.y.into()
+    pub fn try_to_rational(&self) -> Option<Point2D<crate::utils::Rational>> {
+        Some(Point2D::new(
+            crate::utils::Rational::from_f64(self.x)?,
+            crate::utils::Rational::from_f64(self.y)?,
+        ))
     }
 }
 
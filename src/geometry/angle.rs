@@ -0,0 +1,107 @@
+use super::Point2D;
+use crate::num::{Num, Scalar};
+use std::cmp::Ordering;
+
+/// Compare two vectors by their polar angle in [0, 2pi), measured counter-clockwise from the
+/// positive x axis. This is exact for integers, as it only uses the cross product (in the wide
+/// type) instead of `atan2`. The zero vector has angle 0.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+/// use std::cmp::Ordering;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// assert!(polar_cmp(&p(1, 0), &p(0, 1)) == Ordering::Less);
+/// assert!(polar_cmp(&p(-1, -1), &p(1, -1)) == Ordering::Less);
+/// assert!(polar_cmp(&p(2, 2), &p(1, 1)) == Ordering::Equal);
+/// assert!(polar_cmp(&p(-1, 0), &p(0, -1)) == Ordering::Less);
+/// ```
+pub fn polar_cmp<T: Num>(a: &Point2D<T>, b: &Point2D<T>) -> Ordering {
+    let lower = |p: &Point2D<T>| p.y < T::zero() || (p.y.is_zero() && p.x < T::zero());
+    lower(a).cmp(&lower(b)).then_with(|| {
+        let cross = a.widen().cross(b.widen());
+        T::Wide::zero()
+            .partial_cmp(&cross)
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+/// Sort points by their polar angle around origin (see [`polar_cmp`]), points with the same
+/// angle are sorted by their distance to origin.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let mut points = vec![p(1, 4), p(3, 1), p(1, 0), p(2, 1), p(0, 2), p(1, 1)];
+/// polar_sort(&mut points, &p(1, 1));
+/// assert!(points == vec![p(1, 1), p(2, 1), p(3, 1), p(1, 4), p(0, 2), p(1, 0)]);
+/// ```
+pub fn polar_sort<T: Num>(points: &mut [Point2D<T>], origin: &Point2D<T>) {
+    let o = origin.widen();
+    points.sort_by(|a, b| {
+        let (a, b) = (a.widen() - o.clone(), b.widen() - o.clone());
+        polar_cmp(&a, &b).then_with(|| a.len2().partial_cmp(&b.len2()).unwrap_or(Ordering::Equal))
+    });
+}
+
+impl<T> Point2D<T>
+where
+    T: Num,
+{
+    /// Calculate the polar angle in (-pi, pi].
+    pub fn angle(&self) -> f64 {
+        self.y.to_f64().atan2(self.x.to_f64())
+    }
+    /// Calculate the signed angle in (-pi, pi] from self to other, positive if other is
+    /// counter-clockwise of self.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let p = Point2D::new(1i64, 0);
+    /// assert!(p.angle_to(&Point2D::new(0, 5)) == PI / 2.0);
+    /// assert!(p.angle_to(&Point2D::new(-1, -1)) == -3.0 * PI / 4.0);
+    /// ```
+    pub fn angle_to(&self, other: &Point2D<T>) -> f64 {
+        let (a, b) = (self.widen(), other.widen());
+        let cross = a.cross(b.clone()).to_f64();
+        cross.atan2(a.dot(b).to_f64())
+    }
+}
+
+impl Point2D<f64> {
+    /// Rotate counter-clockwise by an angle in radians.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(2.0, 0.0).rotate(std::f64::consts::PI / 3.0);
+    /// assert!((p - Point2D::new(1.0, 3f64.sqrt())).len() < 1e-9);
+    /// ```
+    pub fn rotate(&self, angle: f64) -> Point2D<f64> {
+        let (sin, cos) = angle.sin_cos();
+        Point2D::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+    /// Returns the vector with the same direction and length 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// assert!(Point2D::new(3.0, -4.0).unit() == Point2D::new(0.6, -0.8));
+    /// ```
+    pub fn unit(&self) -> Point2D<f64> {
+        *self / self.len()
+    }
+}
@@ -0,0 +1,275 @@
+use super::{Line, Point2D, EPS};
+use std::f64::consts::PI;
+
+/// A circle with floating point center and radius.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let c = Circle::new(Point2D::new(0.0, 0.0), 5.0);
+/// assert!(c.contains(&Point2D::new(3.0, 4.0)));
+/// assert!(!c.contains(&Point2D::new(3.0, 4.5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point2D<f64>,
+    pub r: f64,
+}
+
+/// Rotate by 90 degrees counter-clockwise.
+fn perp(p: Point2D<f64>) -> Point2D<f64> {
+    Point2D::new(-p.y, p.x)
+}
+
+/// Tangents touching circle 1 and circle 2 (with signed radius r2, negative for the inner
+/// tangents) as pairs of touching points.
+fn tangents(
+    c1: Point2D<f64>,
+    r1: f64,
+    c2: Point2D<f64>,
+    r2: f64,
+) -> Vec<(Point2D<f64>, Point2D<f64>)> {
+    let d = c2 - c1;
+    let dr = r1 - r2;
+    let d2 = d.len2();
+    let h2 = d2 - dr * dr;
+    if d2 < EPS || h2 < -EPS {
+        return vec![];
+    }
+    let h = h2.max(0.0).sqrt();
+    let mut out = vec![];
+    for sign in [-1.0, 1.0] {
+        let v = (d * dr + perp(d) * h * sign) / d2;
+        out.push((c1 + v * r1, c2 + v * r2));
+    }
+    if h < EPS {
+        out.pop();
+    }
+    out
+}
+
+impl Circle {
+    /// Create a new circle.
+    pub fn new(center: Point2D<f64>, r: f64) -> Circle {
+        Circle { center, r }
+    }
+    /// Returns the circle through a, b and c, or None if they are collinear. The test is relative
+    /// to the size of the triangle, so it works the same for tiny and huge coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let c = Circle::circumcircle(Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0), Point2D::new(0.0, 2.0));
+    /// assert!(c.unwrap() == Circle::new(Point2D::new(2.0, 1.0), 5f64.sqrt()));
+    ///
+    /// let tiny = Circle::circumcircle(Point2D::new(0.0, 0.0), Point2D::new(2e-6, 0.0), Point2D::new(0.0, 2e-6));
+    /// assert!((tiny.unwrap().center - Point2D::new(1e-6, 1e-6)).len() < 1e-15);
+    /// let (a, b) = (Point2D::new(0.0, 0.0), Point2D::new(1e9, 1e9));
+    /// assert!(Circle::circumcircle(a, b, Point2D::new(2e9, 2e9 + 1e-3)).is_none());
+    /// assert!(Circle::circumcircle(a, a, b).is_none());
+    /// ```
+    pub fn circumcircle(a: Point2D<f64>, b: Point2D<f64>, c: Point2D<f64>) -> Option<Circle> {
+        let (b, c) = (b - a, c - a);
+        let cross = b.cross(c);
+        // cross / (|b| |c|) is the sine of the angle at a
+        if cross.abs() <= EPS * b.len() * c.len() {
+            return None;
+        }
+        let center = a + perp(b * c.len2() - c * b.len2()) / cross / 2.0;
+        Some(Circle::new(center, (center - a).len()))
+    }
+    /// Calculate the area.
+    pub fn area(&self) -> f64 {
+        PI * self.r * self.r
+    }
+    /// Returns true if p lies inside or on the circle.
+    pub fn contains(&self, p: &Point2D<f64>) -> bool {
+        (*p - self.center).len() <= self.r + EPS
+    }
+    /// Find the intersection points with a line (zero, one or two).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let c = Circle::new(Point2D::new(0.0, 0.0), 5.0);
+    /// let l = Line::new(Point2D::new(-10.0, 3.0), Point2D::new(10.0, 3.0));
+    /// assert!(c.intersect_line(&l) == vec![Point2D::new(-4.0, 3.0), Point2D::new(4.0, 3.0)]);
+    /// let l = Line::new(Point2D::new(5.0, 0.0), Point2D::new(5.0, 1.0));
+    /// assert!(c.intersect_line(&l) == vec![Point2D::new(5.0, 0.0)]);
+    /// ```
+    pub fn intersect_line(&self, line: &Line<f64>) -> Vec<Point2D<f64>> {
+        let proj = line.project(&self.center);
+        let h2 = self.r * self.r - (proj - self.center).len2();
+        if h2 < -EPS {
+            return vec![];
+        }
+        let h = h2.max(0.0).sqrt();
+        if h < EPS {
+            return vec![proj];
+        }
+        let dir = line.b - line.a;
+        let u = dir / dir.len() * h;
+        vec![proj - u, proj + u]
+    }
+    /// Find the intersection points with another circle (zero, one or two). Equal circles have
+    /// no intersection points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let c = Circle::new(Point2D::new(0.0, 0.0), 5.0);
+    /// let d = Circle::new(Point2D::new(8.0, 0.0), 5.0);
+    /// assert!(c.intersect_circle(&d) == vec![Point2D::new(4.0, 3.0), Point2D::new(4.0, -3.0)]);
+    /// let d = Circle::new(Point2D::new(7.0, 0.0), 2.0);
+    /// assert!(c.intersect_circle(&d) == vec![Point2D::new(5.0, 0.0)]);
+    /// ```
+    pub fn intersect_circle(&self, other: &Circle) -> Vec<Point2D<f64>> {
+        let v = other.center - self.center;
+        let d = v.len();
+        if d < EPS || d > self.r + other.r + EPS || d < (self.r - other.r).abs() - EPS {
+            return vec![];
+        }
+        let a = (d * d + self.r * self.r - other.r * other.r) / (2.0 * d);
+        let h = (self.r * self.r - a * a).max(0.0).sqrt();
+        let base = self.center + v * (a / d);
+        if h < EPS {
+            return vec![base];
+        }
+        let off = perp(v) * (h / d);
+        vec![base + off, base - off]
+    }
+    /// Find the points where the tangents through p touch the circle (none if p lies inside,
+    /// p itself if it lies on the circle).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let c = Circle::new(Point2D::new(0.0, 0.0), 1.0);
+    /// let t = c.tangents_from(&Point2D::new(2.0, 0.0));
+    /// let s = 3f64.sqrt() / 2.0;
+    /// assert!((t[0] - Point2D::new(0.5, -s)).len() < 1e-9);
+    /// assert!((t[1] - Point2D::new(0.5, s)).len() < 1e-9);
+    /// ```
+    pub fn tangents_from(&self, p: &Point2D<f64>) -> Vec<Point2D<f64>> {
+        tangents(self.center, self.r, *p, 0.0)
+            .into_iter()
+            .map(|t| t.0)
+            .collect()
+    }
+    /// Find the common tangents of two circles, as pairs of the points where they touch self and
+    /// other. First come the (up to two) outer tangents, then the inner ones. Circles touching
+    /// each other have a single inner (or outer) tangent, equal circles have none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let c = Circle::new(Point2D::new(0.0, 0.0), 1.0);
+    /// let d = Circle::new(Point2D::new(4.0, 0.0), 1.0);
+    /// let t = c.common_tangents(&d);
+    /// assert!(t.len() == 4);
+    /// assert!(t[0] == (Point2D::new(0.0, -1.0), Point2D::new(4.0, -1.0)));
+    /// let d = Circle::new(Point2D::new(2.0, 0.0), 1.0);
+    /// assert!(c.common_tangents(&d).len() == 3);
+    /// ```
+    pub fn common_tangents(&self, other: &Circle) -> Vec<(Point2D<f64>, Point2D<f64>)> {
+        let mut out = tangents(self.center, self.r, other.center, other.r);
+        out.extend(tangents(self.center, self.r, other.center, -other.r));
+        out
+    }
+    /// Calculate the area of the intersection of two circles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let c = Circle::new(Point2D::new(0.0, 0.0), 1.0);
+    /// let d = Circle::new(Point2D::new(1.0, 0.0), 1.0);
+    /// let lens = 2.0 * std::f64::consts::PI / 3.0 - 3f64.sqrt() / 2.0;
+    /// assert!((c.intersection_area(&d) - lens).abs() < 1e-9);
+    /// assert!((c.union_area(&d) - (2.0 * c.area() - lens)).abs() < 1e-9);
+    /// ```
+    pub fn intersection_area(&self, other: &Circle) -> f64 {
+        let d = (other.center - self.center).len();
+        let (r1, r2) = (self.r, other.r);
+        if d >= r1 + r2 {
+            return 0.0;
+        }
+        if d <= (r1 - r2).abs() {
+            let r = r1.min(r2);
+            return PI * r * r;
+        }
+        let a1 = ((d * d + r1 * r1 - r2 * r2) / (2.0 * d * r1))
+            .clamp(-1.0, 1.0)
+            .acos();
+        let a2 = ((d * d + r2 * r2 - r1 * r1) / (2.0 * d * r2))
+            .clamp(-1.0, 1.0)
+            .acos();
+        let kite = ((-d + r1 + r2) * (d + r1 - r2) * (d - r1 + r2) * (d + r1 + r2))
+            .max(0.0)
+            .sqrt();
+        r1 * r1 * a1 + r2 * r2 * a2 - kite / 2.0
+    }
+    /// Calculate the area of the union of two circles.
+    pub fn union_area(&self, other: &Circle) -> f64 {
+        self.area() + other.area() - self.intersection_area(other)
+    }
+}
+
+/// Find the smallest circle enclosing all points with Welzl's algorithm in expected O(n). The
+/// points are shuffled with a fixed seed, so the result is deterministic. Panics if there are
+/// no points.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: f64, y: f64| Point2D::new(x, y);
+/// let c = min_enclosing_circle(&[p(0.0, 0.0), p(4.0, 0.0), p(2.0, 1.0), p(1.0, -1.0)]);
+/// assert!((c.center - p(2.0, 0.0)).len() < 1e-9 && (c.r - 2.0).abs() < 1e-9);
+/// ```
+pub fn min_enclosing_circle(points: &[Point2D<f64>]) -> Circle {
+    assert!(!points.is_empty(), "no points to enclose");
+    let mut p = points.to_vec();
+    let mut seed = 0x9e3779b97f4a7c15u64;
+    for i in (1..p.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        p.swap(i, (seed % (i as u64 + 1)) as usize);
+    }
+    let outside = |c: &Circle, q: &Point2D<f64>| (*q - c.center).len() > c.r * (1.0 + EPS);
+    let mut c = Circle::new(p[0], 0.0);
+    for i in 0..p.len() {
+        if !outside(&c, &p[i]) {
+            continue;
+        }
+        c = Circle::new(p[i], 0.0);
+        for j in 0..i {
+            if !outside(&c, &p[j]) {
+                continue;
+            }
+            let center = (p[i] + p[j]) / 2.0;
+            c = Circle::new(center, (center - p[i]).len());
+            for k in 0..j {
+                if outside(&c, &p[k]) {
+                    c = Circle::circumcircle(p[i], p[j], p[k]).unwrap_or(c);
+                }
+            }
+        }
+    }
+    c
+}
@@ -0,0 +1,309 @@
+use super::{polar_cmp, Line, Point2D, Polygon, EPS};
+use crate::num::{Num, Scalar};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+/// The result of [`half_plane_intersection`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum HalfPlaneIntersection {
+    /// No point lies in all half-planes.
+    Empty,
+    /// The intersection is not empty, but unbounded.
+    Unbounded,
+    /// The intersection is a convex polygon in counter-clockwise order. It can be degenerate,
+    /// e.g. a single point.
+    Bounded(Polygon<f64>),
+}
+
+/// A half-plane as point and direction, the left side is inside.
+#[derive(Debug, Clone, Copy)]
+struct HalfPlane {
+    a: Point2D<f64>,
+    d: Point2D<f64>,
+}
+
+impl HalfPlane {
+    fn out(&self, p: Point2D<f64>) -> bool {
+        self.d.cross(p - self.a) < -EPS
+    }
+    fn intersect(&self, other: &HalfPlane) -> Point2D<f64> {
+        let t = (other.a - self.a).cross(other.d) / self.d.cross(other.d);
+        self.a + self.d * t
+    }
+}
+
+/// Intersects half-planes sorted by angle, returns the vertices or None if it is empty.
+fn intersect_sorted(planes: &[HalfPlane]) -> Option<Vec<Point2D<f64>>> {
+    let mut dq: VecDeque<HalfPlane> = VecDeque::new();
+    for h in planes {
+        while dq.len() > 1 && h.out(dq[dq.len() - 1].intersect(&dq[dq.len() - 2])) {
+            dq.pop_back();
+        }
+        while dq.len() > 1 && h.out(dq[0].intersect(&dq[1])) {
+            dq.pop_front();
+        }
+        if let Some(last) = dq.back() {
+            let cross = last.d.cross(h.d);
+            if cross.abs() < EPS {
+                if h.d.dot(last.d) < 0.0 {
+                    return None;
+                }
+                if !h.out(last.a) {
+                    continue;
+                }
+                dq.pop_back();
+            } else if cross < 0.0 {
+                // h turns back by more than pi, so it cuts off everything that is left
+                return None;
+            }
+        }
+        dq.push_back(*h);
+    }
+    while dq.len() > 2 && dq[0].out(dq[dq.len() - 1].intersect(&dq[dq.len() - 2])) {
+        dq.pop_back();
+    }
+    while dq.len() > 2 && dq[dq.len() - 1].out(dq[0].intersect(&dq[1])) {
+        dq.pop_front();
+    }
+    if dq.len() < 3 {
+        return None;
+    }
+    let n = dq.len();
+    Some((0..n).map(|i| dq[i].intersect(&dq[(i + 1) % n])).collect())
+}
+
+/// Intersect the half-planes to the left of the given lines in O(n log n). The result is
+/// unbounded if all directions of the lines fit into a half-circle, this is decided exactly. The
+/// vertices of the intersection are computed with floats inside a bounding box which is derived
+/// from the coordinates and the smallest angle between two lines, so that it contains all their
+/// intersections.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let mut lines = vec![
+///     Line::new(p(0, 0), p(1, 0)),  // y >= 0
+///     Line::new(p(4, 0), p(4, 1)),  // x <= 4
+///     Line::new(p(0, 4), p(-1, 5)), // x + y <= 4
+/// ];
+/// assert!(half_plane_intersection(&lines) == HalfPlaneIntersection::Unbounded);
+/// lines.push(Line::new(p(0, 1), p(0, 0))); // x >= 0
+/// match half_plane_intersection(&lines) {
+///     HalfPlaneIntersection::Bounded(poly) => assert!(poly.area() == 8.0),
+///     _ => panic!(),
+/// }
+/// lines.push(Line::new(p(5, 0), p(5, -1))); // x >= 5
+/// assert!(half_plane_intersection(&lines) == HalfPlaneIntersection::Empty);
+/// // y >= x + 3 and y <= x
+/// let strip = [Line::new(p(0, 3), p(1, 4)), Line::new(p(-3, -3), p(-5, -5))];
+/// assert!(half_plane_intersection(&strip) == HalfPlaneIntersection::Empty);
+///
+/// // nearly parallel lines only meet far away: the strip 0 <= y <= 1 reaches
+/// // y >= 2 - 0.001x for x >= 1000
+/// let p = |x: f64, y: f64| Point2D::new(x, y);
+/// let lines = [
+///     Line::new(p(0.0, 0.0), p(1.0, 0.0)),
+///     Line::new(p(0.0, 1.0), p(-1.0, 1.0)),
+///     Line::new(p(0.0, 2.0), p(1.0, 1.999)),
+/// ];
+/// assert!(half_plane_intersection(&lines) == HalfPlaneIntersection::Unbounded);
+/// ```
+pub fn half_plane_intersection<T: Num>(lines: &[Line<T>]) -> HalfPlaneIntersection {
+    let mut lines = lines
+        .iter()
+        .map(|l| (l.b.widen() - l.a.widen(), l))
+        .collect::<Vec<_>>();
+    lines.sort_by(|a, b| polar_cmp(&a.0, &b.0));
+    let n = lines.len();
+    // unbounded if there is a gap of at least pi between two consecutive directions
+    let unbounded = (0..n).any(|i| {
+        let (d, e) = (&lines[i].0, &lines[(i + 1) % n].0);
+        let cross = d.cross(e.clone());
+        cross < T::Wide::zero()
+            || (cross.is_zero() && (d.dot(e.clone()) < T::Wide::zero() || n == 1))
+    }) || lines
+        .iter()
+        .all(|l| polar_cmp(&l.0, &lines[0].0) == Ordering::Equal);
+    let mut planes = lines
+        .iter()
+        .map(|(_, l)| HalfPlane {
+            a: l.a.to_f64(),
+            d: l.b.to_f64() - l.a.to_f64(),
+        })
+        .collect::<Vec<_>>();
+    let m = planes
+        .iter()
+        .flat_map(|h| {
+            [
+                h.a.x.abs(),
+                h.a.y.abs(),
+                (h.a + h.d).x.abs(),
+                (h.a + h.d).y.abs(),
+            ]
+        })
+        .fold(1.0, f64::max);
+    // The smallest sine between two non-parallel lines, these are neighbours if the directions
+    // are sorted modulo pi.
+    let mut dirs = lines
+        .iter()
+        .map(|(d, _)| {
+            let zero = T::Wide::zero;
+            if d.y < zero() || (d.y.is_zero() && d.x < zero()) {
+                Point2D::new(zero() - d.x.clone(), zero() - d.y.clone())
+            } else {
+                d.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    dirs.sort_by(polar_cmp);
+    let mut sin = f64::INFINITY;
+    for i in 0..n {
+        let (d, e) = (&dirs[i], &dirs[(i + 1) % n]);
+        let cross = d.cross(e.clone());
+        if !cross.is_zero() {
+            sin = sin.min(cross.to_f64().abs() / d.to_f64().len() / e.to_f64().len());
+        }
+    }
+    // Lines a1 + t d1 and a2 + s d2 meet at t = cross(a2 - a1, d2) / cross(d1, d2), where
+    // |a2 - a1| <= 3m and |cross(d1, d2)| >= |d1| |d2| sin, so |t d1| <= 3m / sin.
+    let bound = if sin.is_finite() {
+        2.0 * (m + 3.0 * m / sin) + 1.0
+    } else {
+        2.0 * m + 1.0
+    };
+    let corners = [
+        (bound, -bound),
+        (bound, bound),
+        (-bound, bound),
+        (-bound, -bound),
+    ];
+    for i in 0..4 {
+        let a = Point2D::new(corners[i].0, corners[i].1);
+        let b = Point2D::new(corners[(i + 1) % 4].0, corners[(i + 1) % 4].1);
+        planes.push(HalfPlane { a, d: b - a });
+    }
+    planes.sort_by(|a, b| polar_cmp(&a.d, &b.d));
+    match intersect_sorted(&planes) {
+        None => HalfPlaneIntersection::Empty,
+        Some(_) if unbounded => HalfPlaneIntersection::Unbounded,
+        Some(points) => HalfPlaneIntersection::Bounded(Polygon::new(points)),
+    }
+}
+
+impl<T> Polygon<T>
+where
+    T: Num,
+{
+    /// Clip the polygon by a convex polygon in counter-clockwise order with the
+    /// Sutherland–Hodgman algorithm in O(nm). The polygon itself doesn't have to be convex,
+    /// but a concave polygon can be split into parts connected by edges on the boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let poly = Polygon::new(vec![p(0, 0), p(4, 0), p(4, 4), p(2, 1), p(0, 4)]);
+    /// let window = Polygon::new(vec![p(0, 0), p(4, 0), p(4, 3), p(0, 3)]);
+    /// // the part of the notch between y = 1 and y = 3 is cut out
+    /// assert!((poly.clip(&window).area() - (12.0 - 4.0 / 3.0 * 2.0)).abs() < 1e-9);
+    /// ```
+    pub fn clip(&self, convex: &Polygon<T>) -> Polygon<f64> {
+        let mut out = self.points.iter().map(|p| p.to_f64()).collect::<Vec<_>>();
+        let n = convex.points.len();
+        for i in 0..n {
+            let a = convex.points[i].to_f64();
+            let d = convex.points[(i + 1) % n].to_f64() - a;
+            let edge = HalfPlane { a, d };
+            let input = std::mem::take(&mut out);
+            for j in 0..input.len() {
+                let (p, q) = (input[j], input[(j + 1) % input.len()]);
+                let (p_in, q_in) = (!edge.out(p), !edge.out(q));
+                if p_in {
+                    out.push(p);
+                }
+                if p_in != q_in {
+                    out.push(edge.intersect(&HalfPlane { a: p, d: q - p }));
+                }
+            }
+        }
+        Polygon::new(out)
+    }
+    /// Intersect two convex polygons in counter-clockwise order with a half-plane intersection
+    /// in O((n + m) log(n + m)). The result is empty if they don't intersect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let a = Polygon::new(vec![p(0, 0), p(4, 0), p(4, 4), p(0, 4)]);
+    /// let b = Polygon::new(vec![p(2, 2), p(6, 2), p(6, 6), p(2, 6)]);
+    /// assert!(a.intersect_convex(&b).area() == 4.0);
+    /// let c = Polygon::new(vec![p(5, 5), p(6, 5), p(6, 6)]);
+    /// assert!(a.intersect_convex(&c).points.is_empty());
+    /// ```
+    pub fn intersect_convex(&self, other: &Polygon<T>) -> Polygon<f64> {
+        let lines = self.edges().chain(other.edges()).map(|e| e.line());
+        match half_plane_intersection(&lines.collect::<Vec<_>>()) {
+            HalfPlaneIntersection::Bounded(poly) => poly,
+            _ => Polygon::new(vec![]),
+        }
+    }
+    /// Compute the Minkowski sum of two convex polygons in counter-clockwise order without
+    /// collinear vertices in O(n + m). The sum contains all points p + q with p in self and q in
+    /// other, it starts at its lowest (then leftmost) vertex.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let square = Polygon::new(vec![p(0, 0), p(1, 0), p(1, 1), p(0, 1)]);
+    /// let triangle = Polygon::new(vec![p(0, 0), p(2, 0), p(0, 2)]);
+    /// let sum = square.minkowski_sum(&triangle);
+    /// assert!(sum.points == vec![p(0, 0), p(3, 0), p(3, 1), p(1, 3), p(0, 3)]);
+    /// ```
+    pub fn minkowski_sum(&self, other: &Polygon<T>) -> Polygon<T> {
+        // start both at the lowest (then leftmost) vertex
+        let start = |p: &Polygon<T>| {
+            let lowest = (0..p.points.len()).min_by(|&i, &j| {
+                let (a, b) = (&p.points[i], &p.points[j]);
+                (&a.y, &a.x)
+                    .partial_cmp(&(&b.y, &b.x))
+                    .expect("cannot compare NaN coordinates")
+            });
+            let mut points = p.points.clone();
+            points.rotate_left(lowest.unwrap_or(0));
+            points
+        };
+        let (p, q) = (start(self), start(other));
+        let (n, m) = (p.len(), q.len());
+        if n == 0 || m == 0 {
+            return Polygon::new(vec![]);
+        }
+        let edge =
+            |v: &[Point2D<T>], i: usize| v[(i + 1) % v.len()].widen() - v[i % v.len()].widen();
+        let mut sum = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            sum.push(p[i % n].clone() + q[j % m].clone());
+            let cross = edge(&p, i).cross(edge(&q, j));
+            let step_p = j == m || (i < n && cross >= T::Wide::zero());
+            let step_q = i == n || (j < m && cross <= T::Wide::zero());
+            if step_p {
+                i += 1;
+            }
+            if step_q {
+                j += 1;
+            }
+        }
+        Polygon::new(sum)
+    }
+}
@@ -0,0 +1,373 @@
+use super::manhattan::kruskal;
+use super::Point2D;
+use std::collections::HashMap;
+
+const NONE: usize = usize::MAX;
+
+/// Quad-edge structure for the divide and conquer of Guibas and Stolfi. Every edge has four
+/// records (the edge, its dual, the reversed edge and the reversed dual), p holds the origin of
+/// primal records as index into the sorted points.
+struct QuadEdges<'a> {
+    pts: &'a [Point2D<i64>],
+    rot: Vec<usize>,
+    o: Vec<usize>,
+    p: Vec<usize>,
+    mark: Vec<bool>,
+}
+
+impl QuadEdges<'_> {
+    fn r(&self, e: usize) -> usize {
+        self.rot[self.rot[e]]
+    }
+    fn prev(&self, e: usize) -> usize {
+        self.rot[self.o[self.rot[e]]]
+    }
+    fn next(&self, e: usize) -> usize {
+        self.prev(self.r(e))
+    }
+    /// The destination of an edge.
+    fn f(&self, e: usize) -> usize {
+        self.p[self.r(e)]
+    }
+    fn cross(&self, p: usize, a: usize, b: usize) -> i128 {
+        let p = self.pts[p].widen();
+        (self.pts[a].widen() - p).cross(self.pts[b].widen() - p)
+    }
+    /// Returns true if p lies strictly inside the circumcircle of the ccw triangle abc.
+    fn circ(&self, p: usize, a: usize, b: usize, c: usize) -> bool {
+        let p = self.pts[p].widen();
+        let [a, b, c] = [a, b, c].map(|i| self.pts[i].widen() - p);
+        a.cross(b) * c.len2() + b.cross(c) * a.len2() + c.cross(a) * b.len2() > 0
+    }
+    fn make_edge(&mut self, orig: usize, dest: usize) -> usize {
+        let e = self.rot.len();
+        for i in 0..4 {
+            self.rot.push(e + (i + 1) % 4);
+            self.p.push(NONE);
+            self.mark.push(false);
+        }
+        self.o.extend([e, e + 3, e + 2, e + 1]);
+        self.p[e] = orig;
+        self.p[e + 2] = dest;
+        e
+    }
+    fn splice(&mut self, a: usize, b: usize) {
+        let (x, y) = (self.rot[self.o[a]], self.rot[self.o[b]]);
+        self.o.swap(x, y);
+        self.o.swap(a, b);
+    }
+    fn connect(&mut self, a: usize, b: usize) -> usize {
+        let q = self.make_edge(self.f(a), self.p[b]);
+        self.splice(q, self.next(a));
+        self.splice(self.r(q), b);
+        q
+    }
+    /// Triangulate the points lo..hi, returns the ccw convex hull edge out of the leftmost
+    /// point and the cw convex hull edge out of the rightmost point.
+    fn rec(&mut self, lo: usize, hi: usize) -> (usize, usize) {
+        if hi - lo <= 3 {
+            let a = self.make_edge(lo, lo + 1);
+            let b = self.make_edge(lo + 1, hi - 1);
+            if hi - lo == 2 {
+                return (a, self.r(a));
+            }
+            self.splice(self.r(a), b);
+            let side = self.cross(lo, lo + 1, lo + 2);
+            if side == 0 {
+                return (a, self.r(b));
+            }
+            let c = self.connect(b, a);
+            return if side < 0 {
+                (self.r(c), c)
+            } else {
+                (a, self.r(b))
+            };
+        }
+        let mid = hi - (hi - lo) / 2;
+        let (mut ra, mut a) = self.rec(lo, mid);
+        let (mut b, mut rb) = self.rec(mid, hi);
+        loop {
+            if self.cross(self.p[b], self.f(a), self.p[a]) < 0 {
+                a = self.next(a);
+            } else if self.cross(self.p[a], self.f(b), self.p[b]) > 0 {
+                b = self.o[self.r(b)];
+            } else {
+                break;
+            }
+        }
+        let mut base = self.connect(self.r(b), a);
+        if self.p[a] == self.p[ra] {
+            ra = self.r(base);
+        }
+        if self.p[b] == self.p[rb] {
+            rb = base;
+        }
+        let valid = |q: &Self, e: usize, base: usize| q.cross(q.f(e), q.f(base), q.p[base]) > 0;
+        loop {
+            let mut lc = self.o[self.r(base)];
+            if valid(self, lc, base) {
+                while self.circ(self.f(self.o[lc]), self.f(base), self.p[base], self.f(lc)) {
+                    let t = self.o[lc];
+                    self.splice(lc, self.prev(lc));
+                    self.splice(self.r(lc), self.prev(self.r(lc)));
+                    lc = t;
+                }
+            }
+            let mut rc = self.prev(base);
+            if valid(self, rc, base) {
+                while self.circ(
+                    self.f(self.prev(rc)),
+                    self.f(base),
+                    self.p[base],
+                    self.f(rc),
+                ) {
+                    let t = self.prev(rc);
+                    self.splice(rc, self.prev(rc));
+                    self.splice(self.r(rc), self.prev(self.r(rc)));
+                    rc = t;
+                }
+            }
+            let (lv, rv) = (valid(self, lc, base), valid(self, rc, base));
+            if !lv && !rv {
+                break;
+            }
+            if !lv || (rv && self.circ(self.f(rc), self.p[rc], self.f(lc), self.p[lc])) {
+                base = self.connect(rc, self.r(base));
+            } else {
+                base = self.connect(self.r(base), self.r(lc));
+            }
+        }
+        (ra, rb)
+    }
+    /// Mark the face left of e and append its vertices to out.
+    fn add_face(&mut self, e: usize, queue: &mut Vec<usize>, out: &mut Vec<usize>) {
+        let mut c = e;
+        loop {
+            self.mark[c] = true;
+            out.push(self.p[c]);
+            queue.push(self.r(c));
+            c = self.next(c);
+            if c == e {
+                break;
+            }
+        }
+    }
+}
+
+/// The points sorted and without duplicates, as indices of their first occurrence.
+fn sorted_unique(points: &[Point2D<i64>]) -> Vec<usize> {
+    let mut order = (0..points.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| (points[i], i));
+    order.dedup_by_key(|&mut i| points[i]);
+    order
+}
+
+/// Compute the Delaunay triangulation with the divide and conquer algorithm of Guibas and
+/// Stolfi in O(n log n): no point lies strictly inside the circumcircle of a triangle. The
+/// triangles are returned counter-clockwise as indices into points, duplicate points are
+/// represented by their first occurrence. If all points are collinear there are no triangles.
+/// The in-circle test is exact in `i128` for coordinates up to 5 * 10^8 in absolute value.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let points = [p(0, 0), p(4, 0), p(4, 4), p(0, 4), p(1, 2)];
+/// let triangles = delaunay(&points);
+/// assert!(triangles.len() == 4);
+/// for t in &triangles {
+///     assert!(orient(&points[t[0]], &points[t[1]], &points[t[2]]) == Orientation::Left);
+/// }
+/// ```
+pub fn delaunay(points: &[Point2D<i64>]) -> Vec<[usize; 3]> {
+    let order = sorted_unique(points);
+    if order.len() < 2 {
+        return vec![];
+    }
+    let pts = order.iter().map(|&i| points[i]).collect::<Vec<_>>();
+    let mut q = QuadEdges {
+        pts: &pts,
+        rot: vec![],
+        o: vec![],
+        p: vec![],
+        mark: vec![],
+    };
+    let mut e = q.rec(0, pts.len()).0;
+    // walk to an edge of the outer face and mark it, so that it isn't returned
+    while q.cross(q.f(q.o[e]), q.f(e), q.p[e]) < 0 {
+        e = q.o[e];
+    }
+    let (mut queue, mut out) = (vec![], vec![]);
+    q.add_face(e, &mut queue, &mut out);
+    out.clear();
+    let mut i = 0;
+    while i < queue.len() {
+        let e = queue[i];
+        i += 1;
+        if !q.mark[e] {
+            q.add_face(e, &mut queue, &mut out);
+        }
+    }
+    out.chunks(3)
+        .map(|t| [order[t[0]], order[t[1]], order[t[2]]])
+        .collect()
+}
+
+/// Returns the edges of the Delaunay triangulation as sorted index pairs.
+fn delaunay_edges(points: &[Point2D<i64>]) -> Vec<(usize, usize)> {
+    let triangles = delaunay(points);
+    let mut edges = if triangles.is_empty() {
+        // all points are collinear, so the triangulation is a path
+        let order = sorted_unique(points);
+        order.windows(2).map(|w| (w[0], w[1])).collect()
+    } else {
+        triangles
+            .iter()
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect::<Vec<_>>()
+    };
+    for e in edges.iter_mut() {
+        *e = (e.0.min(e.1), e.0.max(e.1));
+    }
+    edges.sort();
+    edges.dedup();
+    edges
+}
+
+/// An edge of the Voronoi diagram, it separates the cells of two sites.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoronoiEdge {
+    /// The indices of the two sites.
+    pub sites: (usize, usize),
+    /// The start of the edge, a vertex of the diagram (or a point on the edge for rays).
+    pub from: Point2D<f64>,
+    /// The end of the edge, or None if it is a ray.
+    pub to: Option<Point2D<f64>>,
+    /// The direction from `from` to `to`, or the direction of the ray.
+    pub dir: Point2D<f64>,
+}
+
+/// The center of the circle through a, b and c, which must not be collinear.
+fn circumcenter(a: &Point2D<i64>, b: &Point2D<i64>, c: &Point2D<i64>) -> Point2D<f64> {
+    let (b, c) = (b.widen() - a.widen(), c.widen() - a.widen());
+    let n = b * c.len2() - c * b.len2();
+    let d = 2.0 * b.cross(c) as f64;
+    Point2D::new(a.x as f64 - n.y as f64 / d, a.y as f64 + n.x as f64 / d)
+}
+
+/// Compute the Voronoi diagram as the dual of the Delaunay triangulation in O(n log n). Every
+/// Delaunay edge gives one Voronoi edge between the circumcenters of its two triangles, or a ray
+/// for edges on the convex hull. If all points are collinear, the diagram consists of the
+/// perpendicular bisectors, which are returned as two opposite rays each.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let edges = voronoi_edges(&[p(0, 0), p(4, 0), p(0, 4)]);
+/// assert!(edges.len() == 3 && edges.iter().all(|e| e.to.is_none()));
+/// assert!(edges.iter().all(|e| e.from == Point2D::new(2.0, 2.0)));
+/// let e = edges.iter().find(|e| e.sites == (1, 2)).unwrap();
+/// assert!(e.dir.x > 0.0 && e.dir.x == e.dir.y);
+/// ```
+pub fn voronoi_edges(points: &[Point2D<i64>]) -> Vec<VoronoiEdge> {
+    let triangles = delaunay(points);
+    let mut edges = vec![];
+    if triangles.is_empty() {
+        for (i, j) in delaunay_edges(points) {
+            let from = (points[i].to_f64() + points[j].to_f64()) / 2.0;
+            let d = points[j].to_f64() - points[i].to_f64();
+            for dir in [Point2D::new(-d.y, d.x), Point2D::new(d.y, -d.x)] {
+                let sites = (i, j);
+                let to = None;
+                edges.push(VoronoiEdge {
+                    sites,
+                    from,
+                    to,
+                    dir,
+                });
+            }
+        }
+        return edges;
+    }
+    let mut left_of = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            left_of.insert((tri[k], tri[(k + 1) % 3]), t);
+        }
+    }
+    let centers = triangles
+        .iter()
+        .map(|t| circumcenter(&points[t[0]], &points[t[1]], &points[t[2]]))
+        .collect::<Vec<_>>();
+    for (&(u, v), &t) in &left_of {
+        let sites = (u.min(v), u.max(v));
+        let from = centers[t];
+        match left_of.get(&(v, u)) {
+            Some(&s) if u < v => {
+                let to = centers[s];
+                let dir = to - from;
+                let to = Some(to);
+                edges.push(VoronoiEdge {
+                    sites,
+                    from,
+                    to,
+                    dir,
+                });
+            }
+            Some(_) => {}
+            None => {
+                // the triangle lies left of the hull edge u -> v, so the ray points right
+                let d = points[v].to_f64() - points[u].to_f64();
+                let dir = Point2D::new(d.y, -d.x);
+                let to = None;
+                edges.push(VoronoiEdge {
+                    sites,
+                    from,
+                    to,
+                    dir,
+                });
+            }
+        }
+    }
+    edges.sort_by_key(|e| e.sites);
+    edges
+}
+
+/// Find a minimum spanning tree of the points with Euclidean distances in O(n log n), using that
+/// it is a subgraph of the Delaunay triangulation. Returns the edges as (squared distance, i, j)
+/// with indices into points, duplicate points are connected by edges of length zero.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let points = [p(0, 0), p(3, 4), p(10, 0), p(6, 8), p(3, 4)];
+/// let tree = euclidean_mst(&points);
+/// assert!(tree.len() == 4);
+/// assert!(tree.iter().map(|e| e.0).sum::<i128>() == 25 + 25 + 0 + 65);
+/// ```
+pub fn euclidean_mst(points: &[Point2D<i64>]) -> Vec<(i128, usize, usize)> {
+    let dist2 = |i: usize, j: usize| (points[j].widen() - points[i].widen()).len2();
+    let mut edges = delaunay_edges(points)
+        .into_iter()
+        .map(|(i, j)| (dist2(i, j), i, j))
+        .collect::<Vec<_>>();
+    let order = sorted_unique(points);
+    let mut first = HashMap::new();
+    for &i in &order {
+        first.insert(points[i], i);
+    }
+    for (i, p) in points.iter().enumerate() {
+        if first[p] != i {
+            edges.push((0, first[p], i));
+        }
+    }
+    kruskal(points.len(), edges)
+}
@@ -0,0 +1,304 @@
+use super::Point2D;
+use crate::num::Num;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// A direction on a grid, see [`Dir4`] and [`Dir8`]. The y axis points up (north is (0, 1)), the
+/// same convention [`Point2D::rotcw`] and [`Point2D::rotccw`] use. If the rows of your grid grow
+/// downwards (like in a [`Grid`](super::Grid)), use [`Direction::grid_delta`] instead.
+pub trait Direction: Copy {
+    /// The unit step in this direction, both coordinates are -1, 0 or 1.
+    fn delta(self) -> Point2D<i64>;
+    /// The unit step on a grid whose rows grow downwards, i.e. north moves to the previous row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let d = Dir4::from_char('^').unwrap();
+    /// assert!(d.grid_delta() == Point2D::new(0, -1));
+    /// assert!(d.turn_right().grid_delta() == Point2D::new(1, 0));
+    /// ```
+    fn grid_delta(self) -> Point2D<i64> {
+        let d = self.delta();
+        Point2D::new(d.x, -d.y)
+    }
+}
+
+/// The four directions north, east, south and west.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let d: Dir4 = "R".parse().unwrap();
+/// assert!(d == Dir4::E);
+/// assert!(d.turn_left() == Dir4::N);
+/// assert!(d.turn_right() == Dir4::S);
+/// assert!(d.reverse() == Dir4::W);
+///
+/// // turning right is the same as rotating the step clockwise
+/// let mut step = d.delta();
+/// step.rotcw();
+/// assert!(step == d.turn_right().delta());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Dir4 {
+    /// All directions in clockwise order, starting with north.
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    /// Turn clockwise by 90 degrees.
+    pub fn turn_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+    /// Turn counter-clockwise by 90 degrees.
+    pub fn turn_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+    /// Parse a direction from one of `U/D/L/R`, `^/v/</>` or `N/E/S/W` (letters in any case).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let dirs = "^>v<".chars().map(|c| Dir4::from_char(c).unwrap()).collect::<Vec<_>>();
+    /// assert!(dirs == Dir4::ALL);
+    /// assert!(Dir4::from_char('d') == Some(Dir4::S));
+    /// assert!(Dir4::from_char('x').is_none());
+    /// ```
+    pub fn from_char(c: char) -> Option<Dir4> {
+        match c.to_ascii_uppercase() {
+            'U' | '^' | 'N' => Some(Dir4::N),
+            'R' | '>' | 'E' => Some(Dir4::E),
+            'D' | 'V' | 'S' => Some(Dir4::S),
+            'L' | '<' | 'W' => Some(Dir4::W),
+            _ => None,
+        }
+    }
+}
+
+impl Direction for Dir4 {
+    fn delta(self) -> Point2D<i64> {
+        Dir8::from(self).delta()
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dir4, String> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir4::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| format!("invalid direction: {}", s))
+    }
+}
+
+/// The eight directions including the diagonals.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let d: Dir8 = "ne".parse().unwrap();
+/// assert!(d.delta() == Point2D::new(1, 1));
+/// assert!(d.turn_right() == Dir8::E);
+/// assert!(d.reverse() == Dir8::SW);
+/// assert!("<".parse::<Dir8>().unwrap() == Dir8::from(Dir4::W));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions in clockwise order, starting with north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    /// Turn clockwise by 45 degrees.
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+    /// Turn counter-clockwise by 45 degrees.
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Dir8 {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+impl Direction for Dir8 {
+    fn delta(self) -> Point2D<i64> {
+        const DELTAS: [(i64, i64); 8] = [
+            (0, 1),
+            (1, 1),
+            (1, 0),
+            (1, -1),
+            (0, -1),
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+        ];
+        let (x, y) = DELTAS[self as usize];
+        Point2D::new(x, y)
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = String;
+
+    /// Parse the name of a direction (e.g. `NE` or `sw`), or a single character accepted by
+    /// [`Dir4::from_char`].
+    fn from_str(s: &str) -> Result<Dir8, String> {
+        let name = s.to_ascii_uppercase();
+        Dir8::ALL
+            .into_iter()
+            .find(|d| format!("{:?}", d) == name)
+            .or_else(|| s.parse::<Dir4>().ok().map(Dir8::from))
+            .ok_or_else(|| format!("invalid direction: {}", s))
+    }
+}
+
+impl<T> Point2D<T>
+where
+    T: Num,
+{
+    /// Move n steps into a direction, or None if a coordinate overflows. This also works for
+    /// unsigned coordinates, stepping below zero returns None.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(2usize, 0);
+    /// assert!(p.checked_step(Dir4::W, 2) == Some(Point2D::new(0, 0)));
+    /// assert!(p.checked_step(Dir8::SW, 1).is_none());
+    /// ```
+    pub fn checked_step<D: Direction>(&self, dir: D, n: T) -> Option<Point2D<T>> {
+        let d = dir.delta();
+        let go = |c: &T, s: i64| match s.cmp(&0) {
+            Ordering::Greater => c.checked_add(&n),
+            Ordering::Less => c.checked_sub(&n),
+            Ordering::Equal => Some(c.clone()),
+        };
+        Some(Point2D::new(go(&self.x, d.x)?, go(&self.y, d.y)?))
+    }
+    /// Move n steps into a direction. Panics if a coordinate overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let mut p = Point2D::new(0i64, 0);
+    /// for (d, n) in [(Dir4::N, 3), (Dir4::E, 2), (Dir4::S, 5)] {
+    ///     p = p.step(d, n);
+    /// }
+    /// assert!(p == Point2D::new(2, -2));
+    /// assert!(p.step(Dir8::NW, 2) == Point2D::new(0, 0));
+    /// ```
+    pub fn step<D: Direction>(&self, dir: D, n: T) -> Point2D<T> {
+        self.checked_step(dir, n)
+            .expect("the step overflows a coordinate")
+    }
+    /// Iterate over the four orthogonal neighbours in the order of [`Dir4::ALL`]. Neighbours
+    /// with overflowing coordinates (e.g. below zero for unsigned types) are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Point2D::new(0usize, 3);
+    /// let n = p.neighbors4().collect::<Vec<_>>();
+    /// assert!(n == vec![Point2D::new(0, 4), Point2D::new(1, 3), Point2D::new(0, 2)]);
+    /// ```
+    pub fn neighbors4(&self) -> impl Iterator<Item = Point2D<T>> {
+        let p = self.clone();
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |d| p.checked_step(d, T::one()))
+    }
+    /// Iterate over the eight neighbours including diagonals in the order of [`Dir8::ALL`].
+    /// Neighbours with overflowing coordinates are skipped.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Point2D<T>> {
+        let p = self.clone();
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |d| p.checked_step(d, T::one()))
+    }
+    /// Iterate over the orthogonal neighbours inside the bounds, lo is inclusive and hi is
+    /// exclusive, e.g. (0, 0) and (width, height) for a grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let (lo, hi) = (Point2D::new(0, 0), Point2D::new(3, 2));
+    /// assert!(Point2D::new(2, 1).neighbors4_in(&lo, &hi).count() == 2);
+    /// assert!(Point2D::new(1, 1).neighbors8_in(&lo, &hi).count() == 5);
+    /// ```
+    pub fn neighbors4_in(
+        &self,
+        lo: &Point2D<T>,
+        hi: &Point2D<T>,
+    ) -> impl Iterator<Item = Point2D<T>> {
+        let (lo, hi) = (lo.clone(), hi.clone());
+        self.neighbors4().filter(move |p| p.inside(&lo, &hi))
+    }
+    /// Iterate over all eight neighbours inside the bounds, lo is inclusive and hi is exclusive.
+    pub fn neighbors8_in(
+        &self,
+        lo: &Point2D<T>,
+        hi: &Point2D<T>,
+    ) -> impl Iterator<Item = Point2D<T>> {
+        let (lo, hi) = (lo.clone(), hi.clone());
+        self.neighbors8().filter(move |p| p.inside(&lo, &hi))
+    }
+    fn inside(&self, lo: &Point2D<T>, hi: &Point2D<T>) -> bool {
+        lo.x <= self.x && self.x < hi.x && lo.y <= self.y && self.y < hi.y
+    }
+}
@@ -0,0 +1,146 @@
+use super::Point2D;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A dense grid of width x height cells, indexed by points with x as column and y as row. Row 0
+/// is the first line of the input, so y grows downwards. The directions use y-up, so step with
+/// [`Direction::grid_delta`](super::Direction::grid_delta) on a grid.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let mut grid: Grid<char> = "#.S\n...".parse().unwrap();
+/// assert!((grid.width, grid.height) == (3, 2));
+/// let start = grid.position(|&c| c == 'S').unwrap();
+/// assert!(start == Point2D::new(2, 0));
+/// grid[Point2D::new(0, 1)] = '#';
+/// assert!(grid.get(&Point2D::new(-1, 0)).is_none());
+/// let free = start.neighbors4_in(&Point2D::new(0, 0), &grid.size());
+/// assert!(free.filter(|p| grid[*p] == '.').count() == 2);
+/// assert!(grid.to_string() == "#.S\n#..");
+/// assert!(grid[start + Dir4::S.grid_delta()] == '.');
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    /// The cells in row-major order.
+    pub cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a new grid with all cells set to value.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+    /// Create a grid from its rows, panics if they don't have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let (height, width) = (rows.len(), rows.first().map_or(0, |r| r.len()));
+        assert!(
+            rows.iter().all(|r| r.len() == width),
+            "rows of different length"
+        );
+        let cells = rows.into_iter().flatten().collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+    /// The exclusive upper bound (width, height) of the points inside, e.g. for
+    /// [`Point2D::neighbors4_in`].
+    pub fn size(&self) -> Point2D<i64> {
+        Point2D::new(self.width as i64, self.height as i64)
+    }
+    /// Returns true if p lies inside the grid.
+    pub fn contains(&self, p: &Point2D<i64>) -> bool {
+        0 <= p.x && p.x < self.width as i64 && 0 <= p.y && p.y < self.height as i64
+    }
+    fn index_of(&self, p: &Point2D<i64>) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+    /// Returns the cell at p, or None if p lies outside.
+    pub fn get(&self, p: &Point2D<i64>) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+    /// Returns the cell at p mutably, or None if p lies outside.
+    pub fn get_mut(&mut self, p: &Point2D<i64>) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+    /// Iterate over all points of the grid in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point2D<i64>> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point2D::new(x, y)))
+    }
+    /// Returns the first point in row-major order whose cell satisfies f.
+    pub fn position<F: FnMut(&T) -> bool>(&self, f: F) -> Option<Point2D<i64>> {
+        let i = self.cells.iter().position(f)?;
+        Some(Point2D::new(
+            (i % self.width) as i64,
+            (i / self.width) as i64,
+        ))
+    }
+    /// Iterate over the rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+}
+
+impl<T> Index<Point2D<i64>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D<i64>) -> &T {
+        self.get(&p).expect("point outside of the grid")
+    }
+}
+
+impl<T> IndexMut<Point2D<i64>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2D<i64>) -> &mut T {
+        self.get_mut(&p).expect("point outside of the grid")
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = String;
+
+    /// Parse every line as a row of characters, they must have the same length.
+    fn from_str(s: &str) -> Result<Grid<char>, String> {
+        let rows = s
+            .lines()
+            .map(|l| l.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if rows.windows(2).any(|w| w[0].len() != w[1].len()) {
+            return Err("rows of different length".to_string());
+        }
+        Ok(Grid::from_rows(rows))
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    /// Print the rows on separate lines without separators between the cells.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
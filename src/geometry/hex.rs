@@ -0,0 +1,276 @@
+use super::Point2D;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A hexagon on a hex grid in axial coordinates q and r, the third cube coordinate is
+/// s = -q - r. The axes point 60 degrees apart, the directions in [`Hex::DIRECTIONS`] go
+/// counter-clockwise starting with (1, 0), which is east for pointy-top and north-east for
+/// flat-top hexagons (with the y axis pointing up, as for [`Point2D`]).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// // AoC 2017 day 11
+/// let end = "ne,ne,s,s"
+///     .split(',')
+///     .map(|d| Hex::flat_dir(d).unwrap())
+///     .fold(Hex::default(), |h, d| h + d);
+/// assert!(end.norm() == 2);
+/// // AoC 2020 day 24
+/// let end = Hex::pointy_path("nwwswee").unwrap().into_iter().sum::<Hex>();
+/// assert!(end == Hex::default());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    /// The six unit steps in counter-clockwise order.
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex { q: 1, r: 0 },
+        Hex { q: 0, r: 1 },
+        Hex { q: -1, r: 1 },
+        Hex { q: -1, r: 0 },
+        Hex { q: 0, r: -1 },
+        Hex { q: 1, r: -1 },
+    ];
+
+    /// Create a new hexagon from axial coordinates.
+    pub fn new(q: i64, r: i64) -> Hex {
+        Hex { q, r }
+    }
+    /// Create a hexagon from cube coordinates. Panics if they don't sum up to zero.
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Hex {
+        assert!(q + r + s == 0, "cube coordinates have to sum up to zero");
+        Hex { q, r }
+    }
+    /// Returns the third cube coordinate.
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+    /// Returns the cube coordinates (q, r, s).
+    pub fn cube(&self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+    /// The step for a direction of pointy-top hexagons: `e`, `ne`, `nw`, `w`, `sw` or `se` (in
+    /// any case).
+    pub fn pointy_dir(dir: &str) -> Option<Hex> {
+        let i = ["E", "NE", "NW", "W", "SW", "SE"]
+            .iter()
+            .position(|d| d.eq_ignore_ascii_case(dir))?;
+        Some(Hex::DIRECTIONS[i])
+    }
+    /// The step for a direction of flat-top hexagons: `ne`, `n`, `nw`, `sw`, `s` or `se` (in any
+    /// case).
+    pub fn flat_dir(dir: &str) -> Option<Hex> {
+        let i = ["NE", "N", "NW", "SW", "S", "SE"]
+            .iter()
+            .position(|d| d.eq_ignore_ascii_case(dir))?;
+        Some(Hex::DIRECTIONS[i])
+    }
+    /// Parse pointy-top directions written without separators, e.g. `nwwswee`. Returns None if
+    /// the path contains anything else.
+    pub fn pointy_path(path: &str) -> Option<Vec<Hex>> {
+        let mut steps = vec![];
+        let mut rest = path.trim();
+        while !rest.is_empty() {
+            let len = if rest.starts_with(['n', 's', 'N', 'S']) {
+                2
+            } else {
+                1
+            };
+            steps.push(Hex::pointy_dir(rest.get(..len)?)?);
+            rest = &rest[len..];
+        }
+        Some(steps)
+    }
+    /// Returns the distance to (0, 0), i.e. the number of steps needed.
+    pub fn norm(&self) -> i64 {
+        self.q.abs().max(self.r.abs()).max(self.s().abs())
+    }
+    /// Returns the number of steps between two hexagons.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// assert!(Hex::new(1, -3).dist(&Hex::new(-2, 1)) == 4);
+    /// ```
+    pub fn dist(&self, other: &Hex) -> i64 {
+        (*other - *self).norm()
+    }
+    /// Iterate over the six neighbours in the order of [`Hex::DIRECTIONS`].
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> {
+        let h = *self;
+        Hex::DIRECTIONS.into_iter().map(move |d| h + d)
+    }
+    /// Returns all hexagons at the given distance, counter-clockwise (6 * radius of them).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let center = Hex::new(2, -1);
+    /// let ring = center.ring(3);
+    /// assert!(ring.len() == 18);
+    /// assert!(ring.iter().all(|h| h.dist(&center) == 3));
+    /// assert!(center.ring(0) == vec![center]);
+    /// ```
+    pub fn ring(&self, radius: i64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let mut h = *self + Hex::DIRECTIONS[4] * radius;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for d in Hex::DIRECTIONS {
+            for _ in 0..radius {
+                ring.push(h);
+                h += d;
+            }
+        }
+        ring
+    }
+    /// Returns all hexagons within the given distance ring by ring, starting with self
+    /// (1 + 3 * radius * (radius + 1) of them).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let spiral = Hex::default().spiral(2);
+    /// assert!(spiral.len() == 19);
+    /// assert!(spiral[0] == Hex::default() && spiral[1..7].iter().all(|h| h.norm() == 1));
+    /// ```
+    pub fn spiral(&self, radius: i64) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+    /// Round fractional axial coordinates to the nearest hexagon.
+    pub fn round(q: f64, r: f64) -> Hex {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as i64, rr as i64)
+    }
+    /// Returns the hexagons on the line from self to other, both included. Every two
+    /// consecutive hexagons are neighbours.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let (a, b) = (Hex::new(0, 0), Hex::new(3, -1));
+    /// let line = a.line(&b);
+    /// assert!(line.len() == 4 && line[0] == a && line[3] == b);
+    /// assert!(line.windows(2).all(|w| w[0].dist(&w[1]) == 1));
+    /// ```
+    pub fn line(&self, other: &Hex) -> Vec<Hex> {
+        let n = self.dist(other);
+        if n == 0 {
+            return vec![*self];
+        }
+        // nudge the line, so that it never passes exactly between two hexagons
+        let (q, r) = (self.q as f64 + 1e-6, self.r as f64 + 2e-6);
+        let (dq, dr) = ((other.q - self.q) as f64, (other.r - self.r) as f64);
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                Hex::round(q + dq * t, r + dr * t)
+            })
+            .collect()
+    }
+    /// Returns the center of a pointy-top hexagon with size (center to corner) 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = Hex::pointy_dir("ne").unwrap().pointy_center();
+    /// assert!((p.angle().to_degrees() - 60.0).abs() < 1e-9);
+    /// ```
+    pub fn pointy_center(&self) -> Point2D<f64> {
+        let (q, r) = (self.q as f64, self.r as f64);
+        Point2D::new(3f64.sqrt() * (q + r / 2.0), 1.5 * r)
+    }
+    /// Returns the center of a flat-top hexagon with size (center to corner) 1.
+    pub fn flat_center(&self) -> Point2D<f64> {
+        let (q, r) = (self.q as f64, self.r as f64);
+        Point2D::new(1.5 * q, 3f64.sqrt() * (r + q / 2.0))
+    }
+}
+
+impl From<Point2D<i64>> for Hex {
+    /// Use x and y as the axial coordinates q and r.
+    fn from(p: Point2D<i64>) -> Hex {
+        Hex::new(p.x, p.y)
+    }
+}
+
+impl From<Hex> for Point2D<i64> {
+    /// Use the axial coordinates q and r as x and y.
+    fn from(h: Hex) -> Point2D<i64> {
+        Point2D::new(h.q, h.r)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Hex {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Hex {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Hex) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Hex {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl std::iter::Sum for Hex {
+    fn sum<I: Iterator<Item = Hex>>(iter: I) -> Hex {
+        iter.fold(Hex::default(), |a, b| a + b)
+    }
+}
@@ -0,0 +1,274 @@
+use super::{orient, Orientation, Point2D, PointLocation, Polygon, Segment};
+use crate::num::{Num, Scalar};
+
+/// Compute the convex hull of the points with Andrew's monotone chain in O(n log n). The hull is
+/// returned in counter-clockwise order, starting at the smallest point (by x, then y). With
+/// `keep_collinear` points on the edges of the hull are included as well. If all points are
+/// collinear the hull is a line segment, i.e. just its endpoints (or all points on it in order).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let points = vec![p(0, 0), p(2, 0), p(4, 0), p(1, 1), p(4, 4), p(0, 4), p(2, 2)];
+/// assert!(convex_hull(&points, false).points == vec![p(0, 0), p(4, 0), p(4, 4), p(0, 4)]);
+/// assert!(convex_hull(&points, true).points == vec![p(0, 0), p(2, 0), p(4, 0), p(4, 4), p(0, 4)]);
+/// ```
+pub fn convex_hull<T: Num>(points: &[Point2D<T>], keep_collinear: bool) -> Polygon<T> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.partial_cmp(b).expect("cannot sort NaN coordinates"));
+    points.dedup();
+    if points.len() < 3 {
+        return Polygon::new(points);
+    }
+    let first = &points[0];
+    let last = &points[points.len() - 1];
+    if points
+        .iter()
+        .all(|p| orient(first, last, p) == Orientation::Collinear)
+    {
+        if !keep_collinear {
+            points = vec![first.clone(), last.clone()];
+        }
+        return Polygon::new(points);
+    }
+    // pops while the last two points and p don't make a left turn
+    let pops = |a: &Point2D<T>, b: &Point2D<T>, p: &Point2D<T>| match orient(a, b, p) {
+        Orientation::Left => false,
+        Orientation::Right => true,
+        Orientation::Collinear => !keep_collinear,
+    };
+    let mut hull: Vec<Point2D<T>> = vec![];
+    for p in &points {
+        while hull.len() >= 2 && pops(&hull[hull.len() - 2], &hull[hull.len() - 1], p) {
+            hull.pop();
+        }
+        hull.push(p.clone());
+    }
+    let lower = hull.len();
+    for p in points.iter().rev().skip(1) {
+        while hull.len() > lower && pops(&hull[hull.len() - 2], &hull[hull.len() - 1], p) {
+            hull.pop();
+        }
+        hull.push(p.clone());
+    }
+    hull.pop();
+    Polygon::new(hull)
+}
+
+/// Squared distance of two points in the wide type.
+fn dist2<T: Num>(a: &Point2D<T>, b: &Point2D<T>) -> T::Wide {
+    (b.widen() - a.widen()).len2()
+}
+
+/// Rotating calipers on convex polygons. They expect the vertices in counter-clockwise order
+/// without collinear points, as returned by [`convex_hull`].
+impl<T> Polygon<T>
+where
+    T: Num,
+{
+    /// Find the farthest pair of vertices (the diameter) with rotating calipers in O(n). Returns
+    /// the squared distance and the pair. Panics if the polygon is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let hull = convex_hull(&[p(0, 0), p(5, 1), p(6, 3), p(1, 2), p(3, 3)], false);
+    /// let (d2, a, b) = hull.farthest_pair();
+    /// assert!(d2 == 45);
+    /// assert!((a, b) == (p(0, 0), p(6, 3)) || (a, b) == (p(6, 3), p(0, 0)));
+    /// ```
+    pub fn farthest_pair(&self) -> (T::Wide, Point2D<T>, Point2D<T>) {
+        let p = &self.points;
+        let n = p.len();
+        assert!(n > 0, "the polygon is empty");
+        let mut best = (T::Wide::zero(), 0, 0);
+        let mut j = if n < 2 { 0 } else { 1 };
+        let mut i = 0;
+        while i < j {
+            loop {
+                let d = dist2(&p[i], &p[j]);
+                if d > best.0 {
+                    best = (d, i, j);
+                }
+                let edge = p[i + 1].widen() - p[i].widen();
+                let next = p[(j + 1) % n].widen() - p[j].widen();
+                if next.cross(edge) >= T::Wide::zero() {
+                    break;
+                }
+                j = (j + 1) % n;
+            }
+            i += 1;
+        }
+        (best.0, p[best.1].clone(), p[best.2].clone())
+    }
+    /// Returns the index of the vertex which maximizes f, starting the search at j and moving
+    /// forward as long as f doesn't decrease (f has to be unimodal along the polygon).
+    fn advance<F: Fn(&Point2D<T>) -> T::Wide>(&self, mut j: usize, f: F) -> usize {
+        let n = self.points.len();
+        for _ in 0..n {
+            let next = (j + 1) % n;
+            if f(&self.points[next]) < f(&self.points[j]) {
+                break;
+            }
+            j = next;
+        }
+        j
+    }
+    /// Calls f with the edge index, the vertex farthest from the edge, and the vertices with the
+    /// largest and smallest projection onto the edge.
+    fn calipers<F: FnMut(usize, usize, usize, usize)>(&self, mut f: F) {
+        let p = &self.points;
+        let n = p.len();
+        let (mut top, mut right, mut left) = (1, 1, 1);
+        for i in 0..n {
+            let a = p[i].widen();
+            let e = p[(i + 1) % n].widen() - a.clone();
+            let height = |q: &Point2D<T>| e.cross(q.widen() - a.clone());
+            let along = |q: &Point2D<T>| e.dot(q.widen() - a.clone());
+            right = self.advance(right, along);
+            if i == 0 {
+                top = right;
+            }
+            top = self.advance(top, height);
+            if i == 0 {
+                left = top;
+            }
+            left = self.advance(left, |q| T::Wide::zero() - along(q));
+            f(i, top, right, left);
+        }
+    }
+    /// Calculate the minimum width, i.e. the smallest distance between two parallel lines
+    /// enclosing the polygon, with rotating calipers in O(n).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let hull = convex_hull(&[p(0, 0), p(10, 0), p(10, 2), p(5, 3), p(0, 2)], false);
+    /// assert!(hull.min_width() == 3.0);
+    /// ```
+    pub fn min_width(&self) -> f64 {
+        if self.points.len() < 3 {
+            return 0.0;
+        }
+        let p = &self.points;
+        let n = p.len();
+        let mut best = f64::INFINITY;
+        self.calipers(|i, top, _, _| {
+            let edge = Segment::new(p[i].clone(), p[(i + 1) % n].clone());
+            best = best.min(edge.line().dist(&p[top]));
+        });
+        best
+    }
+    /// Find the rectangle with the smallest area enclosing the polygon with rotating calipers in
+    /// O(n). One of its sides lies on an edge of the polygon. Returns the area and the corners in
+    /// counter-clockwise order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let hull = convex_hull(&[p(0, 2), p(2, 0), p(4, 2), p(2, 4), p(1, 1)], false);
+    /// let (area, corners) = hull.min_area_rect();
+    /// assert!((area - 8.0).abs() < 1e-9);
+    /// assert!(corners.iter().all(|c| (c.x - c.x.round()).abs() < 1e-9));
+    /// ```
+    pub fn min_area_rect(&self) -> (f64, [Point2D<f64>; 4]) {
+        let p = &self.points;
+        let n = p.len();
+        if n < 3 {
+            let a = p
+                .first()
+                .map(|a| a.to_f64())
+                .unwrap_or(Point2D::new(0.0, 0.0));
+            let b = p.last().map(|b| b.to_f64()).unwrap_or(a);
+            return (0.0, [a, b, b, a]);
+        }
+        let mut best = (f64::INFINITY, [Point2D::new(0.0, 0.0); 4]);
+        self.calipers(|i, top, right, left| {
+            let a = p[i].to_f64();
+            let e = p[(i + 1) % n].to_f64() - a;
+            let u = e / e.len();
+            let v = Point2D::new(-u.y, u.x);
+            let height = v.dot(p[top].to_f64() - a);
+            let hi = u.dot(p[right].to_f64() - a);
+            let lo = u.dot(p[left].to_f64() - a);
+            let area = height * (hi - lo);
+            if area < best.0 {
+                let c1 = a + u * lo;
+                let c2 = a + u * hi;
+                best = (area, [c1, c2, c2 + v * height, c1 + v * height]);
+            }
+        });
+        best
+    }
+    /// Find out if p lies inside, outside or on the boundary of the convex polygon with a binary
+    /// search in O(log n).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let hull = convex_hull(&[p(0, 0), p(4, 0), p(6, 3), p(3, 5), p(0, 3)], false);
+    /// assert!(hull.locate_convex(&p(3, 2)) == PointLocation::Inside);
+    /// assert!(hull.locate_convex(&p(0, 1)) == PointLocation::OnBoundary);
+    /// assert!(hull.locate_convex(&p(5, 1)) == PointLocation::Outside);
+    /// assert!(hull.locate_convex(&p(0, 0)) == PointLocation::OnBoundary);
+    /// ```
+    pub fn locate_convex(&self, p: &Point2D<T>) -> PointLocation {
+        let v = &self.points;
+        let n = v.len();
+        if n < 3 {
+            let on = match n {
+                0 => false,
+                1 => v[0] == *p,
+                _ => Segment::new(v[0].clone(), v[1].clone()).contains(p),
+            };
+            return if on {
+                PointLocation::OnBoundary
+            } else {
+                PointLocation::Outside
+            };
+        }
+        let o = &v[0];
+        if orient(o, &v[1], p) == Orientation::Right || orient(o, &v[n - 1], p) == Orientation::Left
+        {
+            return PointLocation::Outside;
+        }
+        // the last a with p not right of o -> v[a]
+        let (mut a, mut b) = (1, n - 1);
+        while b - a > 1 {
+            let c = (a + b) / 2;
+            if orient(o, &v[c], p) == Orientation::Right {
+                b = c;
+            } else {
+                a = c;
+            }
+        }
+        match orient(&v[a], &v[a + 1], p) {
+            Orientation::Right => PointLocation::Outside,
+            Orientation::Collinear => PointLocation::OnBoundary,
+            Orientation::Left => {
+                let first = a == 1 && orient(o, &v[1], p) == Orientation::Collinear;
+                let last = a + 1 == n - 1 && orient(o, &v[n - 1], p) == Orientation::Collinear;
+                if first || last {
+                    PointLocation::OnBoundary
+                } else {
+                    PointLocation::Inside
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use super::{orient, Orientation, Point2D};
+use crate::num::{Num, Scalar};
+
+/// A line segment between the points a and b (both included).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let s = Segment::new(Point2D::new(0i64, 0), Point2D::new(4, 4));
+/// let t = Segment::new(Point2D::new(0, 4), Point2D::new(4, 0));
+/// assert!(s.intersection_kind(&t) == SegmentIntersection::Proper);
+/// assert!(s.intersection_f64(&t) == Some(Point2D::new(2.0, 2.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    pub a: Point2D<T>,
+    pub b: Point2D<T>,
+}
+
+/// An infinite line through the points a and b, which have to be different. It is directed from
+/// a to b, which defines its left and right side.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let l = Line::new(Point2D::new(0i64, 0), Point2D::new(2, 0));
+/// assert!(l.side(&Point2D::new(5, 1)) == Orientation::Left);
+/// assert!(l.reflect(&Point2D::new(5, 1)) == Point2D::new(5.0, -1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line<T> {
+    pub a: Point2D<T>,
+    pub b: Point2D<T>,
+}
+
+/// How two segments intersect, see [`Segment::intersection_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentIntersection {
+    /// The segments have no common point.
+    Disjoint,
+    /// The segments cross in a single point which is not an endpoint of either of them.
+    Proper,
+    /// The segments have a single common point which is an endpoint of at least one of them.
+    Touching,
+    /// The segments are collinear and share more than one point.
+    Overlapping,
+}
+
+impl<T> Segment<T> {
+    /// Create a new segment from a to b.
+    pub fn new(a: Point2D<T>, b: Point2D<T>) -> Segment<T> {
+        Segment { a, b }
+    }
+}
+
+impl<T> Line<T> {
+    /// Create a new line through a and b.
+    pub fn new(a: Point2D<T>, b: Point2D<T>) -> Line<T> {
+        Line { a, b }
+    }
+}
+
+/// Returns true if p lies between a and b (in any order).
+fn between<T: PartialOrd>(a: &T, b: &T, p: &T) -> bool {
+    (a <= p && p <= b) || (b <= p && p <= a)
+}
+
+/// Intersection of the lines ab and cd in floats, None if they are parallel.
+fn intersect_f64<T: Num>(
+    a: &Point2D<T>,
+    b: &Point2D<T>,
+    c: &Point2D<T>,
+    d: &Point2D<T>,
+) -> Option<Point2D<f64>> {
+    let (aw, cw) = (a.widen(), c.widen());
+    let cd = d.widen() - cw.clone();
+    let denom = (b.widen() - aw.clone()).cross(cd.clone());
+    if denom.is_zero() {
+        return None;
+    }
+    let t = (cw - aw).cross(cd).to_f64() / denom.to_f64();
+    let a = a.to_f64();
+    Some(a + (b.to_f64() - a) * t)
+}
+
+/// Exact intersection of the lines ab and cd, None if they are parallel.
+#[cfg(feature = "rug")]
+fn intersect_rational<T: Num>(
+    a: &Point2D<T>,
+    b: &Point2D<T>,
+    c: &Point2D<T>,
+    d: &Point2D<T>,
+) -> Option<Point2D<crate::utils::Rational>> {
+    let (a, c) = (a.to_rational(), c.to_rational());
+    let ab = b.to_rational() - a.clone();
+    let cd = d.to_rational() - c.clone();
+    let denom = ab.cross(cd.clone());
+    if denom.is_zero() {
+        return None;
+    }
+    let t = (c - a.clone()).cross(cd) / denom;
+    Some(a + ab * t)
+}
+
+impl<T> Segment<T>
+where
+    T: Num,
+{
+    /// Returns the line through both endpoints.
+    pub fn line(&self) -> Line<T> {
+        Line::new(self.a.clone(), self.b.clone())
+    }
+    /// Calculate the length of the segment.
+    pub fn len(&self) -> f64 {
+        (self.b.to_f64() - self.a.to_f64()).len()
+    }
+    /// Returns true if p lies on the segment (endpoints included). This is exact for integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let s = Segment::new(Point2D::new(0i64, 0), Point2D::new(4, 2));
+    /// assert!(s.contains(&Point2D::new(2, 1)));
+    /// assert!(s.contains(&Point2D::new(4, 2)));
+    /// assert!(!s.contains(&Point2D::new(6, 3)));
+    /// assert!(!s.contains(&Point2D::new(1, 1)));
+    /// ```
+    pub fn contains(&self, p: &Point2D<T>) -> bool {
+        orient(&self.a, &self.b, p) == Orientation::Collinear
+            && between(&self.a.x, &self.b.x, &p.x)
+            && between(&self.a.y, &self.b.y, &p.y)
+    }
+    /// Find out how two segments intersect. This is exact for integers, segments consisting of
+    /// a single point are supported as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// let s = Segment::new(p(0, 0), p(4, 0));
+    /// let kind = |a, b| s.intersection_kind(&Segment::new(a, b));
+    /// assert!(kind(p(2, -1), p(2, 1)) == SegmentIntersection::Proper);
+    /// assert!(kind(p(2, 0), p(2, 1)) == SegmentIntersection::Touching);
+    /// assert!(kind(p(4, 0), p(6, 0)) == SegmentIntersection::Touching);
+    /// assert!(kind(p(3, 0), p(6, 0)) == SegmentIntersection::Overlapping);
+    /// assert!(kind(p(5, 0), p(6, 0)) == SegmentIntersection::Disjoint);
+    /// assert!(kind(p(2, 1), p(3, 1)) == SegmentIntersection::Disjoint);
+    /// ```
+    pub fn intersection_kind(&self, other: &Segment<T>) -> SegmentIntersection {
+        let (a, b, c, d) = (&self.a, &self.b, &other.a, &other.b);
+        let (o1, o2) = (orient(a, b, c), orient(a, b, d));
+        let (o3, o4) = (orient(c, d, a), orient(c, d, b));
+        let collinear = Orientation::Collinear;
+        if [o1, o2, o3, o4].iter().all(|&o| o == collinear) {
+            // on a common line the lexicographic order is the order along the line
+            let (lo1, hi1) = if a <= b { (a, b) } else { (b, a) };
+            let (lo2, hi2) = if c <= d { (c, d) } else { (d, c) };
+            let lo = if lo1 >= lo2 { lo1 } else { lo2 };
+            let hi = if hi1 <= hi2 { hi1 } else { hi2 };
+            return if lo > hi {
+                SegmentIntersection::Disjoint
+            } else if lo == hi {
+                SegmentIntersection::Touching
+            } else {
+                SegmentIntersection::Overlapping
+            };
+        }
+        if o1 != o2 && o3 != o4 {
+            if [o1, o2, o3, o4].contains(&collinear) {
+                SegmentIntersection::Touching
+            } else {
+                SegmentIntersection::Proper
+            }
+        } else if self.contains(c) || self.contains(d) || other.contains(a) || other.contains(b) {
+            SegmentIntersection::Touching
+        } else {
+            SegmentIntersection::Disjoint
+        }
+    }
+    /// Returns the endpoint which lies on the other segment, if the segments touch.
+    fn touching_point(&self, other: &Segment<T>) -> Option<Point2D<T>> {
+        [&other.a, &other.b]
+            .into_iter()
+            .find(|p| self.contains(p))
+            .or_else(|| [&self.a, &self.b].into_iter().find(|p| other.contains(p)))
+            .cloned()
+    }
+    /// Compute the intersection point of two segments in floats. Returns None if they don't
+    /// intersect or overlap in more than one point. Touching points are returned exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let s = Segment::new(Point2D::new(0i64, 0), Point2D::new(3, 3));
+    /// let t = Segment::new(Point2D::new(0, 1), Point2D::new(3, 1));
+    /// assert!(s.intersection_f64(&t) == Some(Point2D::new(1.0, 1.0)));
+    /// let t = Segment::new(Point2D::new(2, 2), Point2D::new(5, 5));
+    /// assert!(s.intersection_f64(&t).is_none());
+    /// ```
+    pub fn intersection_f64(&self, other: &Segment<T>) -> Option<Point2D<f64>> {
+        match self.intersection_kind(other) {
+            SegmentIntersection::Proper => intersect_f64(&self.a, &self.b, &other.a, &other.b),
+            SegmentIntersection::Touching => self.touching_point(other).map(|p| p.to_f64()),
+            _ => None,
+        }
+    }
+    /// Compute the exact intersection point of two segments as rationals. Returns None if they
+    /// don't intersect or overlap in more than one point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    /// use crate::cp_rs::utils::Rational;
+    ///
+    /// let s = Segment::new(Point2D::new(0i64, 0), Point2D::new(3, 1));
+    /// let t = Segment::new(Point2D::new(0, 1), Point2D::new(1, 0));
+    /// let p = s.intersection_rational(&t).unwrap();
+    /// assert!(p == Point2D::new(Rational::from((3, 4)), Rational::from((1, 4))));
+    /// ```
+    #[cfg(feature = "rug")]
+    pub fn intersection_rational(
+        &self,
+        other: &Segment<T>,
+    ) -> Option<Point2D<crate::utils::Rational>> {
+        match self.intersection_kind(other) {
+            SegmentIntersection::Proper => intersect_rational(&self.a, &self.b, &other.a, &other.b),
+            SegmentIntersection::Touching => self.touching_point(other).map(|p| p.to_rational()),
+            _ => None,
+        }
+    }
+    /// Find the point of the segment which is closest to p.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let s = Segment::new(Point2D::new(0i64, 0), Point2D::new(4, 0));
+    /// assert!(s.closest_point(&Point2D::new(1, 3)) == Point2D::new(1.0, 0.0));
+    /// assert!(s.closest_point(&Point2D::new(-2, 3)) == Point2D::new(0.0, 0.0));
+    /// ```
+    pub fn closest_point(&self, p: &Point2D<T>) -> Point2D<f64> {
+        let a = self.a.widen();
+        let ab = self.b.widen() - a.clone();
+        let t = (p.widen() - a).dot(ab.clone());
+        let l = ab.len2();
+        if t <= T::Wide::zero() || l.is_zero() {
+            self.a.to_f64()
+        } else if t >= l {
+            self.b.to_f64()
+        } else {
+            let a = self.a.to_f64();
+            a + (self.b.to_f64() - a) * (t.to_f64() / l.to_f64())
+        }
+    }
+    /// Calculate the distance from p to the closest point of the segment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let s = Segment::new(Point2D::new(0i64, 0), Point2D::new(4, 0));
+    /// assert!(s.dist(&Point2D::new(2, -3)) == 3.0);
+    /// assert!(s.dist(&Point2D::new(7, 4)) == 5.0);
+    /// ```
+    pub fn dist(&self, p: &Point2D<T>) -> f64 {
+        (self.closest_point(p) - p.to_f64()).len()
+    }
+}
+
+impl<T> Line<T>
+where
+    T: Num,
+{
+    /// Returns on which side of the line p lies, [`Orientation::Left`] is to the left when
+    /// looking from a to b.
+    pub fn side(&self, p: &Point2D<T>) -> Orientation {
+        orient(&self.a, &self.b, p)
+    }
+    /// Returns true if p lies on the line. This is exact for integers.
+    pub fn contains(&self, p: &Point2D<T>) -> bool {
+        self.side(p) == Orientation::Collinear
+    }
+    /// Returns true if both lines are parallel (or equal).
+    pub fn is_parallel(&self, other: &Line<T>) -> bool {
+        let d1 = self.b.widen() - self.a.widen();
+        let d2 = other.b.widen() - other.a.widen();
+        d1.cross(d2).is_zero()
+    }
+    /// Compute the intersection point of two lines in floats, None if they are parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let l = Line::new(Point2D::new(0i64, 0), Point2D::new(1, 1));
+    /// let m = Line::new(Point2D::new(0, 3), Point2D::new(1, 3));
+    /// assert!(l.intersection_f64(&m) == Some(Point2D::new(3.0, 3.0)));
+    /// assert!(l.intersection_f64(&l).is_none());
+    /// ```
+    pub fn intersection_f64(&self, other: &Line<T>) -> Option<Point2D<f64>> {
+        intersect_f64(&self.a, &self.b, &other.a, &other.b)
+    }
+    /// Compute the exact intersection point of two lines as rationals, None if they are
+    /// parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    /// use crate::cp_rs::utils::Rational;
+    ///
+    /// let l = Line::new(Point2D::new(0i64, 0), Point2D::new(3, 1));
+    /// let m = Line::new(Point2D::new(0, 1), Point2D::new(1, 0));
+    /// let p = l.intersection_rational(&m).unwrap();
+    /// assert!(p == Point2D::new(Rational::from((3, 4)), Rational::from((1, 4))));
+    /// ```
+    #[cfg(feature = "rug")]
+    pub fn intersection_rational(
+        &self,
+        other: &Line<T>,
+    ) -> Option<Point2D<crate::utils::Rational>> {
+        intersect_rational(&self.a, &self.b, &other.a, &other.b)
+    }
+    /// Find where two lines intersect as times t and s with a + (b - a) * t = other.a +
+    /// (other.b - other.a) * s. They are returned exactly as (t, s, denominator) in the wide
+    /// type, the denominator is positive. Returns None if the lines are parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// // AoC 2023 day 24: hailstones at 19, 13 @ -2, 1 and 18, 19 @ -1, -1
+    /// let (p, v) = (Point2D::new(19i64, 13), Point2D::new(-2, 1));
+    /// let (q, w) = (Point2D::new(18, 19), Point2D::new(-1, -1));
+    /// let (t, s, den) = Line::new(p, p + v).intersection_times(&Line::new(q, q + w)).unwrap();
+    /// assert!(3 * t == 7 * den && 3 * s == 11 * den);
+    /// ```
+    pub fn intersection_times(&self, other: &Line<T>) -> Option<(T::Wide, T::Wide, T::Wide)> {
+        let a = self.a.widen();
+        let ab = self.b.widen() - a.clone();
+        let cd = other.b.widen() - other.a.widen();
+        let ac = other.a.widen() - a;
+        let den = ab.cross(cd.clone());
+        if den.is_zero() {
+            return None;
+        }
+        let (t, s) = (ac.cross(cd), ac.cross(ab));
+        if den < T::Wide::zero() {
+            let zero = T::Wide::zero;
+            return Some((zero() - t, zero() - s, zero() - den));
+        }
+        Some((t, s, den))
+    }
+    /// Calculate the distance from p to the line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let l = Line::new(Point2D::new(0i64, 0), Point2D::new(4, 0));
+    /// assert!(l.dist(&Point2D::new(7, -3)) == 3.0);
+    /// ```
+    pub fn dist(&self, p: &Point2D<T>) -> f64 {
+        let a = self.a.widen();
+        let ab = self.b.widen() - a.clone();
+        ab.cross(p.widen() - a).abs().to_f64() / ab.len()
+    }
+    /// Project p orthogonally onto the line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let l = Line::new(Point2D::new(0i64, 0), Point2D::new(1, 1));
+    /// assert!(l.project(&Point2D::new(2, 0)) == Point2D::new(1.0, 1.0));
+    /// ```
+    pub fn project(&self, p: &Point2D<T>) -> Point2D<f64> {
+        let a = self.a.widen();
+        let ab = self.b.widen() - a.clone();
+        let t = (p.widen() - a).dot(ab.clone()).to_f64() / ab.len2().to_f64();
+        let a = self.a.to_f64();
+        a + (self.b.to_f64() - a) * t
+    }
+    /// Reflect p at the line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let l = Line::new(Point2D::new(0i64, 0), Point2D::new(1, 1));
+    /// assert!(l.reflect(&Point2D::new(2, 0)) == Point2D::new(0.0, 2.0));
+    /// ```
+    pub fn reflect(&self, p: &Point2D<T>) -> Point2D<f64> {
+        self.project(p) * 2.0 - p.to_f64()
+    }
+}
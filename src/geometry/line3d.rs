@@ -0,0 +1,84 @@
+use super::Point3D;
+use crate::num::{Num, Scalar};
+
+/// An infinite line in 3D through the points a and b, which have to be different. With
+/// `Rational` coordinates all computations are exact, which is useful if the products of
+/// coordinates overflow even the wide type.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let l = Line3D::new(Point3D::new(0i64, 0, 0), Point3D::new(1, 1, 1));
+/// let m = Line3D::new(Point3D::new(4, 0, 2), Point3D::new(3, 1, 2));
+/// let (t, s, den) = l.intersection_times(&m).unwrap();
+/// assert!((t / den, s / den) == (2, 2));
+/// let skew = Line3D::new(Point3D::new(4, 0, 3), Point3D::new(3, 1, 3));
+/// assert!(l.intersection_times(&skew).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line3D<T> {
+    pub a: Point3D<T>,
+    pub b: Point3D<T>,
+}
+
+impl<T> Line3D<T> {
+    /// Create a new line through a and b.
+    pub fn new(a: Point3D<T>, b: Point3D<T>) -> Line3D<T> {
+        Line3D { a, b }
+    }
+}
+
+impl<T> Line3D<T>
+where
+    T: Num,
+{
+    /// Returns true if the lines are parallel (or equal).
+    pub fn is_parallel(&self, other: &Line3D<T>) -> bool {
+        let d1 = self.b.widen() - self.a.widen();
+        let d2 = other.b.widen() - other.a.widen();
+        d1.cross(d2).len2().is_zero()
+    }
+    /// Find where two lines intersect as times t and s with a + (b - a) * t = other.a +
+    /// (other.b - other.a) * s. They are returned exactly as (t, s, denominator) in the wide
+    /// type, the denominator is positive. Returns None if the lines are parallel or skew. The
+    /// intermediate products have degree four in the coordinates, for large `i64` coordinates
+    /// convert them to `Rational` first.
+    pub fn intersection_times(&self, other: &Line3D<T>) -> Option<(T::Wide, T::Wide, T::Wide)> {
+        let a = self.a.widen();
+        let d = self.b.widen() - a.clone();
+        let e = other.b.widen() - other.a.widen();
+        let ac = other.a.widen() - a;
+        let n = d.cross(e.clone());
+        let den = n.len2();
+        if den.is_zero() || !ac.dot(n.clone()).is_zero() {
+            return None;
+        }
+        Some((ac.cross(e).dot(n.clone()), ac.cross(d).dot(n), den))
+    }
+    /// Compute the exact intersection point of two lines as rationals, None if they are
+    /// parallel or skew.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    /// use crate::cp_rs::utils::Rational;
+    ///
+    /// let l = Line3D::new(Point3D::new(0i64, 0, 0), Point3D::new(3, 0, 3));
+    /// let m = Line3D::new(Point3D::new(0, 1, 1), Point3D::new(1, 0, 1));
+    /// let p = l.intersection_rational(&m).unwrap();
+    /// assert!(p == Point3D::new(Rational::from(1), Rational::from(0), Rational::from(1)));
+    /// ```
+    #[cfg(feature = "rug")]
+    pub fn intersection_rational(
+        &self,
+        other: &Line3D<T>,
+    ) -> Option<Point3D<crate::utils::Rational>> {
+        let l = Line3D::new(self.a.to_rational(), self.b.to_rational());
+        let m = Line3D::new(other.a.to_rational(), other.b.to_rational());
+        let (t, _, den) = l.intersection_times(&m)?;
+        Some(l.a.clone() + (l.b - l.a) * (t / den))
+    }
+}
@@ -0,0 +1,165 @@
+use super::{Point2D, PointN};
+use crate::num::Num;
+use std::collections::BTreeMap;
+
+impl<T> Point2D<T>
+where
+    T: Num,
+{
+    /// Calculate the Manhattan distance to other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let (a, b) = (Point2D::new(1u64, 5), Point2D::new(4, 1));
+    /// assert!(a.manhattan(&b) == 7);
+    /// assert!(a.chebyshev(&b) == 4);
+    /// ```
+    pub fn manhattan(&self, other: &Point2D<T>) -> T {
+        let (a, b) = (self.clone(), other.clone());
+        PointN::new([a.x, a.y]).manhattan(&PointN::new([b.x, b.y]))
+    }
+    /// Calculate the Chebyshev distance (maximum norm) to other.
+    pub fn chebyshev(&self, other: &Point2D<T>) -> T {
+        let (a, b) = (self.clone(), other.clone());
+        PointN::new([a.x, a.y]).chebyshev(&PointN::new([b.x, b.y]))
+    }
+    /// Rotate by 45 degrees (and scale) into u = x + y and v = x - y. The Manhattan distance of
+    /// two points is the Chebyshev distance of their transformed points, so Manhattan balls turn
+    /// into axis-aligned squares.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let (a, b) = (Point2D::new(1, 5), Point2D::new(4, 1));
+    /// assert!(a.to_uv() == Point2D::new(6, -4));
+    /// assert!(a.to_uv().chebyshev(&b.to_uv()) == a.manhattan(&b));
+    /// assert!(Point2D::from_uv(&a.to_uv()) == a);
+    /// ```
+    pub fn to_uv(&self) -> Point2D<T> {
+        Point2D::new(
+            self.x.clone() + self.y.clone(),
+            self.x.clone() - self.y.clone(),
+        )
+    }
+    /// Undo [`Point2D::to_uv`]. For integers this is only exact if u and v have the same parity.
+    pub fn from_uv(uv: &Point2D<T>) -> Point2D<T> {
+        let two = T::one() + T::one();
+        Point2D::new(
+            (uv.x.clone() + uv.y.clone()) / two.clone(),
+            (uv.x.clone() - uv.y.clone()) / two,
+        )
+    }
+}
+
+/// Find the parts of row y covered by the Manhattan balls given as center and radius, as sorted
+/// and disjoint inclusive ranges of x (adjacent ranges are merged).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// // AoC 2022 day 15: a sensor at (8, 7) with its beacon at (2, 10)
+/// let sensor = Point2D::new(8, 7);
+/// let balls = [(sensor, sensor.manhattan(&Point2D::new(2, 10))), (Point2D::new(20, 1), 3)];
+/// assert!(manhattan_row_cover(&balls, 10) == vec![(2, 14)]);
+/// assert!(manhattan_row_cover(&balls, 0) == vec![(6, 10), (18, 22)]);
+/// ```
+pub fn manhattan_row_cover(balls: &[(Point2D<i64>, i64)], y: i64) -> Vec<(i64, i64)> {
+    let mut ranges = balls
+        .iter()
+        .filter_map(|(c, r)| {
+            let w = r - (c.y - y).abs();
+            (w >= 0).then(|| (c.x - w, c.x + w))
+        })
+        .collect::<Vec<_>>();
+    ranges.sort();
+    let mut merged: Vec<(i64, i64)> = vec![];
+    for (lo, hi) in ranges {
+        match merged.last_mut() {
+            Some(last) if lo <= last.1 + 1 => last.1 = last.1.max(hi),
+            _ => merged.push((lo, hi)),
+        }
+    }
+    merged
+}
+
+/// Kruskal's algorithm: returns the edges (weight, i, j) of a minimum spanning forest of n
+/// vertices.
+pub(super) fn kruskal<W: PartialOrd>(
+    n: usize,
+    mut edges: Vec<(W, usize, usize)>,
+) -> Vec<(W, usize, usize)> {
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    edges.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("cannot compare NaN weights"));
+    let mut parent = (0..n).collect::<Vec<_>>();
+    let mut tree = vec![];
+    for (w, i, j) in edges {
+        let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+        if a != b {
+            parent[a] = b;
+            tree.push((w, i, j));
+        }
+    }
+    tree
+}
+
+/// Find a minimum spanning tree of the points with Manhattan distances in O(n log n). Only
+/// O(n) candidate edges are needed: for every point the closest one in each of the eight
+/// octants around it. Returns the edges as (distance, i, j) with indices into points.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let points = [p(0, 0), p(5, 1), p(2, 2), p(6, 6), p(0, 3)];
+/// let tree = manhattan_mst(&points);
+/// assert!(tree.len() == 4);
+/// assert!(tree.iter().map(|e| e.0).sum::<i64>() == 3 + 3 + 4 + 6);
+/// ```
+pub fn manhattan_mst(points: &[Point2D<i64>]) -> Vec<(i64, usize, usize)> {
+    let mut ps = points.to_vec();
+    let mut id = (0..ps.len()).collect::<Vec<_>>();
+    let mut edges = vec![];
+    for k in 0..4 {
+        id.sort_by_key(|&i| ps[i].x + ps[i].y);
+        // points which haven't found their closest point in the octant yet, keyed by -y
+        let mut sweep: BTreeMap<i64, usize> = BTreeMap::new();
+        for &i in &id {
+            let mut done = vec![];
+            for (&key, &j) in sweep.range(-ps[i].y..) {
+                let d = ps[i] - ps[j];
+                if d.y > d.x {
+                    break;
+                }
+                edges.push((d.x + d.y, i, j));
+                done.push(key);
+            }
+            for key in done {
+                sweep.remove(&key);
+            }
+            sweep.insert(-ps[i].y, i);
+        }
+        for p in ps.iter_mut() {
+            if k & 1 == 1 {
+                p.x = -p.x;
+            } else {
+                std::mem::swap(&mut p.x, &mut p.y);
+            }
+        }
+    }
+    kruskal(points.len(), edges)
+}
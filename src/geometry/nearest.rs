@@ -0,0 +1,263 @@
+use super::Point2D;
+use crate::num::{Num, Scalar};
+use std::cmp::Ordering;
+
+/// The closest pair found so far: squared distance and the two points.
+type Best<T> = Option<(<T as Num>::Wide, Point2D<T>, Point2D<T>)>;
+
+fn cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).expect("cannot compare NaN coordinates")
+}
+
+/// Returns true if the squared distance d is smaller than the best one.
+fn closer<T: Num>(best: &Best<T>, d: &T::Wide) -> bool {
+    match best {
+        Some(b) => *d < b.0,
+        None => true,
+    }
+}
+
+fn update<T: Num>(best: &mut Best<T>, a: &Point2D<T>, b: &Point2D<T>) {
+    let d = (b.widen() - a.widen()).len2();
+    if closer(best, &d) {
+        *best = Some((d, a.clone(), b.clone()));
+    }
+}
+
+/// Divide and conquer on points sorted by x, which are sorted by y afterwards.
+fn closest_rec<T: Num>(p: &mut [Point2D<T>], best: &mut Best<T>) {
+    let n = p.len();
+    if n <= 3 {
+        for i in 0..n {
+            for j in i + 1..n {
+                update(best, &p[i], &p[j]);
+            }
+        }
+        p.sort_by(|a, b| cmp(&a.y, &b.y));
+        return;
+    }
+    let mid = n / 2;
+    let mx = p[mid].x.widen();
+    let (left, right) = p.split_at_mut(mid);
+    closest_rec(left, best);
+    closest_rec(right, best);
+    let mut merged = Vec::with_capacity(n);
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() || j < right.len() {
+        if j == right.len() || (i < left.len() && left[i].y <= right[j].y) {
+            merged.push(left[i].clone());
+            i += 1;
+        } else {
+            merged.push(right[j].clone());
+            j += 1;
+        }
+    }
+    p.clone_from_slice(&merged);
+    let strip = p
+        .iter()
+        .filter(|q| {
+            let dx = q.x.widen() - mx.clone();
+            closer(best, &(dx.clone() * dx))
+        })
+        .collect::<Vec<_>>();
+    for i in 0..strip.len() {
+        for j in i + 1..strip.len() {
+            let dy = strip[j].y.widen() - strip[i].y.widen();
+            if !closer(best, &(dy.clone() * dy)) {
+                break;
+            }
+            update(best, strip[i], strip[j]);
+        }
+    }
+}
+
+/// Find the closest pair of points with divide and conquer in O(n log n). Returns the squared
+/// distance (exact for integers, as it is computed in the wide type) and the pair, or None if
+/// there are less than two points.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let (d2, a, b) = closest_pair(&[p(0, 0), p(10, 10), p(4, 7), p(12, 9), p(-3, 5)]).unwrap();
+/// assert!(d2 == 5);
+/// assert!((a, b) == (p(10, 10), p(12, 9)) || (a, b) == (p(12, 9), p(10, 10)));
+/// assert!(closest_pair(&[p(1, 1)]).is_none());
+/// ```
+pub fn closest_pair<T: Num>(points: &[Point2D<T>]) -> Option<(T::Wide, Point2D<T>, Point2D<T>)> {
+    let mut p = points.to_vec();
+    p.sort_by(|a, b| cmp(&a.x, &b.x));
+    let mut best = None;
+    closest_rec(&mut p, &mut best);
+    best
+}
+
+/// A static k-d tree over points with attached values. It answers nearest neighbour, k-nearest
+/// neighbour and rectangle queries, the distances are squared and computed in the wide type of
+/// T, so they are exact for integers.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let tree = KdTree::new(vec![
+///     (Point2D::new(0i64, 0), "a"),
+///     (Point2D::new(5, 5), "b"),
+///     (Point2D::new(9, 1), "c"),
+///     (Point2D::new(2, 8), "d"),
+/// ]);
+/// let (d2, p, v) = tree.nearest(&Point2D::new(6, 3)).unwrap();
+/// assert!((d2, *p, *v) == (5, Point2D::new(5, 5), "b"));
+///
+/// let tree = KdTree::new(vec![(Point2D::new(0.5, 0.5), 1), (Point2D::new(2.0, 0.0), 2)]);
+/// assert!(*tree.nearest(&Point2D::new(1.5, 0.0)).unwrap().2 == 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KdTree<T, V> {
+    /// The points in tree order: the middle of every range splits it.
+    items: Vec<(Point2D<T>, V)>,
+}
+
+/// Returns the x coordinate on even and the y coordinate on odd depths.
+fn coord<T>(p: &Point2D<T>, depth: usize) -> &T {
+    if depth & 1 == 0 {
+        &p.x
+    } else {
+        &p.y
+    }
+}
+
+fn build<T: Num, V>(items: &mut [(Point2D<T>, V)], depth: usize) {
+    if items.len() <= 1 {
+        return;
+    }
+    let mid = items.len() / 2;
+    items.select_nth_unstable_by(mid, |a, b| cmp(coord(&a.0, depth), coord(&b.0, depth)));
+    let (left, right) = items.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+impl<T, V> KdTree<T, V>
+where
+    T: Num,
+{
+    /// Build a tree from points and their values in O(n log n).
+    pub fn new(mut items: Vec<(Point2D<T>, V)>) -> KdTree<T, V> {
+        build(&mut items, 0);
+        KdTree { items }
+    }
+    /// Returns the number of points in the tree.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    /// Returns true if the tree contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// Returns all points and values, in no particular order.
+    pub fn items(&self) -> &[(Point2D<T>, V)] {
+        &self.items
+    }
+    /// Find the point closest to q. Returns its squared distance, the point and its value.
+    pub fn nearest(&self, q: &Point2D<T>) -> Option<(T::Wide, &Point2D<T>, &V)> {
+        self.k_nearest(q, 1).pop()
+    }
+    /// Find the k points closest to q, sorted by their squared distance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let points = (0..10i64).map(|i| (Point2D::new(i, i * i), i)).collect();
+    /// let tree = KdTree::new(points);
+    /// let near = tree.k_nearest(&Point2D::new(0, 0), 3);
+    /// assert!(near.iter().map(|(_, _, &v)| v).collect::<Vec<_>>() == vec![0, 1, 2]);
+    /// assert!(near[2].0 == 20);
+    /// ```
+    pub fn k_nearest(&self, q: &Point2D<T>, k: usize) -> Vec<(T::Wide, &Point2D<T>, &V)> {
+        let mut found = Vec::with_capacity(k + 1);
+        if k > 0 {
+            Self::k_nearest_rec(&self.items, 0, q, k, &mut found);
+        }
+        found
+    }
+    fn k_nearest_rec<'a>(
+        items: &'a [(Point2D<T>, V)],
+        depth: usize,
+        q: &Point2D<T>,
+        k: usize,
+        found: &mut Vec<(T::Wide, &'a Point2D<T>, &'a V)>,
+    ) {
+        if items.is_empty() {
+            return;
+        }
+        let mid = items.len() / 2;
+        let (p, v) = &items[mid];
+        let d = (p.widen() - q.widen()).len2();
+        if found.len() < k || d < found[found.len() - 1].0 {
+            let pos = found.partition_point(|f| f.0 <= d);
+            found.insert(pos, (d, p, v));
+            found.truncate(k);
+        }
+        let diff = coord(q, depth).widen() - coord(p, depth).widen();
+        let (near, far) = if diff < T::Wide::zero() {
+            (&items[..mid], &items[mid + 1..])
+        } else {
+            (&items[mid + 1..], &items[..mid])
+        };
+        Self::k_nearest_rec(near, depth + 1, q, k, found);
+        if found.len() < k || diff.clone() * diff < found[found.len() - 1].0 {
+            Self::k_nearest_rec(far, depth + 1, q, k, found);
+        }
+    }
+    /// Find all points in the rectangle spanned by lo and hi (borders included).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let points = (0..10).map(|i| (Point2D::new(i, 9 - i), i)).collect();
+    /// let tree = KdTree::new(points);
+    /// let mut inside = tree
+    ///     .range(&Point2D::new(2, 2), &Point2D::new(5, 9))
+    ///     .iter()
+    ///     .map(|(_, &v)| v)
+    ///     .collect::<Vec<_>>();
+    /// inside.sort();
+    /// assert!(inside == vec![2, 3, 4, 5]);
+    /// ```
+    pub fn range(&self, lo: &Point2D<T>, hi: &Point2D<T>) -> Vec<(&Point2D<T>, &V)> {
+        let mut found = vec![];
+        Self::range_rec(&self.items, 0, lo, hi, &mut found);
+        found
+    }
+    fn range_rec<'a>(
+        items: &'a [(Point2D<T>, V)],
+        depth: usize,
+        lo: &Point2D<T>,
+        hi: &Point2D<T>,
+        found: &mut Vec<(&'a Point2D<T>, &'a V)>,
+    ) {
+        if items.is_empty() {
+            return;
+        }
+        let mid = items.len() / 2;
+        let (p, v) = &items[mid];
+        if lo.x <= p.x && p.x <= hi.x && lo.y <= p.y && p.y <= hi.y {
+            found.push((p, v));
+        }
+        let c = coord(p, depth);
+        if coord(lo, depth) <= c {
+            Self::range_rec(&items[..mid], depth + 1, lo, hi, found);
+        }
+        if c <= coord(hi, depth) {
+            Self::range_rec(&items[mid + 1..], depth + 1, lo, hi, found);
+        }
+    }
+}
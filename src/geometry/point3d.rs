@@ -0,0 +1,236 @@
+use super::PointN;
+use crate::num::Num;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
+
+/// A point in 3D, it supports the same operations as [`Point2D`](super::Point2D).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let mut p = Point3D::new(1, 2, 3);
+/// p += Point3D::new(1, 1, 1) * 2;
+/// assert!(p == Point3D::new(3, 4, 5));
+/// assert!(p % 2 - Point3D::new(1, 0, 1) == Point3D::new(0, 0, 0));
+/// assert!(p.manhattan(&Point3D::new(0, 0, 0)) == 12);
+/// ```
+#[derive(Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Point3D<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Display for Point3D<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Debug for Point3D<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Point3D<T> {
+    /// Create a new 3d point with generic type T
+    pub fn new(x: T, y: T, z: T) -> Point3D<T> {
+        Point3D { x, y, z }
+    }
+}
+
+impl<T> Add<Point3D<T>> for Point3D<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Point3D<T>) -> Self {
+        Point3D::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T> AddAssign<Point3D<T>> for Point3D<T>
+where
+    T: Add<Output = T> + Clone,
+{
+    fn add_assign(&mut self, other: Self) {
+        *self = self.clone() + other;
+    }
+}
+
+impl<T> Sub<Point3D<T>> for Point3D<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Point3D<T>) -> Self {
+        Point3D::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T> SubAssign<Point3D<T>> for Point3D<T>
+where
+    T: Sub<Output = T> + Clone,
+{
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.clone() - other;
+    }
+}
+
+impl<T, S> Mul<S> for Point3D<T>
+where
+    T: Mul<S, Output = T>,
+    S: Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self {
+        Point3D::new(self.x * rhs.clone(), self.y * rhs.clone(), self.z * rhs)
+    }
+}
+
+impl<T, S> MulAssign<S> for Point3D<T>
+where
+    T: Mul<S, Output = T> + Clone,
+    S: Clone,
+{
+    fn mul_assign(&mut self, other: S) {
+        *self = self.clone() * other;
+    }
+}
+
+impl<T, S> Div<S> for Point3D<T>
+where
+    T: Div<S, Output = T>,
+    S: Clone,
+{
+    type Output = Self;
+
+    fn div(self, rhs: S) -> Self {
+        Point3D::new(self.x / rhs.clone(), self.y / rhs.clone(), self.z / rhs)
+    }
+}
+
+impl<T, S> DivAssign<S> for Point3D<T>
+where
+    T: Div<S, Output = T> + Clone,
+    S: Clone,
+{
+    fn div_assign(&mut self, other: S) {
+        *self = self.clone() / other;
+    }
+}
+
+impl<T, S> Rem<S> for Point3D<T>
+where
+    T: Rem<S, Output = T>,
+    S: Clone,
+{
+    type Output = Self;
+
+    fn rem(self, rhs: S) -> Self {
+        Point3D::new(self.x % rhs.clone(), self.y % rhs.clone(), self.z % rhs)
+    }
+}
+
+impl<T, S> RemAssign<S> for Point3D<T>
+where
+    T: Rem<S, Output = T> + Clone,
+    S: Clone,
+{
+    fn rem_assign(&mut self, other: S) {
+        *self = self.clone() % other;
+    }
+}
+
+impl<T> Point3D<T>
+where
+    T: Num,
+{
+    /// Calculate the length of a point (Euclidean distance to (0, 0, 0)).
+    pub fn len(&self) -> f64 {
+        self.to_f64().len2().sqrt()
+    }
+    /// Calculate the squared length of a point, this stays exact for integers.
+    pub fn len2(&self) -> T {
+        self.dot(self.clone())
+    }
+    /// Compute the dot product of two points.
+    pub fn dot(&self, other: Point3D<T>) -> T {
+        self.x.clone() * other.x + self.y.clone() * other.y + self.z.clone() * other.z
+    }
+    /// Compute the cross product of two points, it is orthogonal to both and its length is the
+    /// area of the spanned parallelogram.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let x = Point3D::new(1, 0, 0);
+    /// let y = Point3D::new(0, 1, 0);
+    /// assert!(x.cross(y) == Point3D::new(0, 0, 1));
+    /// assert!(y.cross(x) == Point3D::new(0, 0, -1));
+    /// ```
+    pub fn cross(&self, other: Point3D<T>) -> Point3D<T> {
+        let (a, b) = (self.clone(), other);
+        Point3D::new(
+            a.y.clone() * b.z.clone() - a.z.clone() * b.y.clone(),
+            a.z * b.x.clone() - a.x.clone() * b.z,
+            a.x * b.y - a.y * b.x,
+        )
+    }
+    /// Calculate the Manhattan distance to other.
+    pub fn manhattan(&self, other: &Point3D<T>) -> T {
+        PointN::from(self.clone()).manhattan(&PointN::from(other.clone()))
+    }
+    /// Calculate the Chebyshev distance (maximum norm) to other.
+    pub fn chebyshev(&self, other: &Point3D<T>) -> T {
+        PointN::from(self.clone()).chebyshev(&PointN::from(other.clone()))
+    }
+    /// Convert the coordinates into the wide type of T (see [`Num::Wide`]).
+    pub fn widen(&self) -> Point3D<T::Wide> {
+        Point3D::new(self.x.widen(), self.y.widen(), self.z.widen())
+    }
+    /// Convert the coordinates into floats.
+    pub fn to_f64(&self) -> Point3D<f64> {
+        Point3D::new(self.x.to_f64(), self.y.to_f64(), self.z.to_f64())
+    }
+    /// Convert the coordinates into exact rationals. Panics for NaN and infinite floats.
+    #[cfg(feature = "rug")]
+    pub fn to_rational(&self) -> Point3D<crate::utils::Rational> {
+        Point3D::new(
+            self.x.to_rational(),
+            self.y.to_rational(),
+            self.z.to_rational(),
+        )
+    }
+    /// Iterate over the 26 neighbours including diagonals, see [`PointN::neighbors`].
+    pub fn neighbors(&self) -> impl Iterator<Item = Point3D<T>> {
+        PointN::from(self.clone()).neighbors().map(Point3D::from)
+    }
+}
+
+impl<T> From<Point3D<T>> for PointN<T, 3> {
+    fn from(p: Point3D<T>) -> PointN<T, 3> {
+        PointN::new([p.x, p.y, p.z])
+    }
+}
+
+impl<T> From<PointN<T, 3>> for Point3D<T> {
+    fn from(p: PointN<T, 3>) -> Point3D<T> {
+        let [x, y, z] = p.coords;
+        Point3D::new(x, y, z)
+    }
+}
@@ -0,0 +1,276 @@
+use crate::num::{Num, Scalar};
+use std::fmt::{Debug, Display};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// A point with N coordinates, e.g. for 4D Conway cubes. It supports the same operations as
+/// [`Point2D`](super::Point2D) and can be indexed by the dimension.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let mut p = PointN::new([1, 2, 3, 4]);
+/// p -= PointN::new([1, 1, 1, 1]);
+/// p[3] *= 2;
+/// assert!(p == PointN::new([0, 1, 2, 6]));
+/// assert!(p * 2 / 3 == PointN::new([0, 0, 1, 4]));
+/// ```
+#[derive(Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PointN<T, const N: usize> {
+    pub coords: [T; N],
+}
+
+impl<T, const N: usize> Display for PointN<T, N>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.coords.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T, const N: usize> Debug for PointN<T, N>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.coords.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T, const N: usize> PointN<T, N> {
+    /// Create a new point from its coordinates.
+    pub fn new(coords: [T; N]) -> PointN<T, N> {
+        PointN { coords }
+    }
+    /// Combine the coordinates of two points pairwise.
+    fn zip<U, F: FnMut(T, T) -> U>(self, other: PointN<T, N>, mut f: F) -> PointN<U, N> {
+        let mut other = other.coords.into_iter();
+        PointN::new(self.coords.map(|a| f(a, other.next().unwrap())))
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PointN<T, N> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.coords[i]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.coords[i]
+    }
+}
+
+impl<T, const N: usize> Add<PointN<T, N>> for PointN<T, N>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: PointN<T, N>) -> Self {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl<T, const N: usize> AddAssign<PointN<T, N>> for PointN<T, N>
+where
+    T: Add<Output = T> + Clone,
+{
+    fn add_assign(&mut self, other: Self) {
+        *self = self.clone() + other;
+    }
+}
+
+impl<T, const N: usize> Sub<PointN<T, N>> for PointN<T, N>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: PointN<T, N>) -> Self {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+impl<T, const N: usize> SubAssign<PointN<T, N>> for PointN<T, N>
+where
+    T: Sub<Output = T> + Clone,
+{
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.clone() - other;
+    }
+}
+
+impl<T, S, const N: usize> Mul<S> for PointN<T, N>
+where
+    T: Mul<S, Output = T>,
+    S: Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self {
+        PointN::new(self.coords.map(|a| a * rhs.clone()))
+    }
+}
+
+impl<T, S, const N: usize> MulAssign<S> for PointN<T, N>
+where
+    T: Mul<S, Output = T> + Clone,
+    S: Clone,
+{
+    fn mul_assign(&mut self, other: S) {
+        *self = self.clone() * other;
+    }
+}
+
+impl<T, S, const N: usize> Div<S> for PointN<T, N>
+where
+    T: Div<S, Output = T>,
+    S: Clone,
+{
+    type Output = Self;
+
+    fn div(self, rhs: S) -> Self {
+        PointN::new(self.coords.map(|a| a / rhs.clone()))
+    }
+}
+
+impl<T, S, const N: usize> DivAssign<S> for PointN<T, N>
+where
+    T: Div<S, Output = T> + Clone,
+    S: Clone,
+{
+    fn div_assign(&mut self, other: S) {
+        *self = self.clone() / other;
+    }
+}
+
+impl<T, S, const N: usize> Rem<S> for PointN<T, N>
+where
+    T: Rem<S, Output = T>,
+    S: Clone,
+{
+    type Output = Self;
+
+    fn rem(self, rhs: S) -> Self {
+        PointN::new(self.coords.map(|a| a % rhs.clone()))
+    }
+}
+
+impl<T, S, const N: usize> RemAssign<S> for PointN<T, N>
+where
+    T: Rem<S, Output = T> + Clone,
+    S: Clone,
+{
+    fn rem_assign(&mut self, other: S) {
+        *self = self.clone() % other;
+    }
+}
+
+/// The absolute difference, which also works for unsigned types.
+fn abs_diff<T: Num>(a: &T, b: &T) -> T {
+    if a < b {
+        b.clone() - a.clone()
+    } else {
+        a.clone() - b.clone()
+    }
+}
+
+impl<T, const N: usize> PointN<T, N>
+where
+    T: Num,
+{
+    /// The point with all coordinates zero.
+    pub fn zero() -> PointN<T, N> {
+        PointN::new(std::array::from_fn(|_| T::zero()))
+    }
+    /// Calculate the Manhattan distance to other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let (a, b) = (PointN::new([1u32, 5, 2]), PointN::new([4, 3, 2]));
+    /// assert!(a.manhattan(&b) == 5);
+    /// assert!(a.chebyshev(&b) == 3);
+    /// assert!(a.dist2(&b) == 13);
+    /// ```
+    pub fn manhattan(&self, other: &PointN<T, N>) -> T {
+        (0..N).fold(T::zero(), |acc, i| acc + abs_diff(&self[i], &other[i]))
+    }
+    /// Calculate the Chebyshev distance (maximum norm) to other.
+    pub fn chebyshev(&self, other: &PointN<T, N>) -> T {
+        (0..N).fold(T::zero(), |acc, i| {
+            let d = abs_diff(&self[i], &other[i]);
+            if d > acc {
+                d
+            } else {
+                acc
+            }
+        })
+    }
+    /// Calculate the squared Euclidean distance in the wide type, this stays exact for integers.
+    pub fn dist2(&self, other: &PointN<T, N>) -> T::Wide {
+        (0..N).fold(T::Wide::zero(), |acc, i| {
+            let d = other[i].widen() - self[i].widen();
+            acc + d.clone() * d
+        })
+    }
+    /// Calculate the Euclidean distance to other.
+    pub fn dist(&self, other: &PointN<T, N>) -> f64 {
+        self.dist2(other).to_f64().sqrt()
+    }
+    /// Iterate over the 3^N - 1 neighbours, i.e. all points which differ by at most one in
+    /// every coordinate. Neighbours with overflowing coordinates (e.g. below zero for unsigned
+    /// types) are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = PointN::new([0i64, 0, 0, 0]);
+    /// assert!(p.neighbors().count() == 80);
+    /// assert!(p.neighbors().all(|q| q.chebyshev(&p) == 1));
+    /// assert!(PointN::new([0usize, 5]).neighbors().count() == 5);
+    /// ```
+    pub fn neighbors(&self) -> impl Iterator<Item = PointN<T, N>> {
+        let p = self.clone();
+        let total = 3usize.pow(N as u32);
+        (0..total)
+            .filter(move |&k| k != total / 2)
+            .filter_map(move |mut k| {
+                let mut q = p.clone();
+                for c in q.coords.iter_mut() {
+                    *c = match k % 3 {
+                        0 => c.checked_sub(&T::one())?,
+                        1 => c.clone(),
+                        _ => c.checked_add(&T::one())?,
+                    };
+                    k /= 3;
+                }
+                Some(q)
+            })
+    }
+}
@@ -0,0 +1,266 @@
+use super::{orient, Orientation, Point2D, Segment};
+use crate::num::{gcd, Num, Scalar};
+
+/// A polygon given by its vertices in order (the first vertex is not repeated at the end). Most
+/// methods assume that the polygon is simple, i.e. that its edges don't cross.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let poly = Polygon::new(vec![
+///     Point2D::new(0i64, 0),
+///     Point2D::new(4, 0),
+///     Point2D::new(4, 3),
+///     Point2D::new(0, 3),
+/// ]);
+/// assert!(poly.area2() == 24);
+/// assert!(poly.perimeter() == 14.0);
+/// assert!(poly.locate(&Point2D::new(2, 1)) == PointLocation::Inside);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon<T> {
+    pub points: Vec<Point2D<T>>,
+}
+
+/// Where a point lies relative to a polygon, see [`Polygon::locate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointLocation {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+impl<T> Polygon<T> {
+    /// Create a new polygon from its vertices.
+    pub fn new(points: Vec<Point2D<T>>) -> Polygon<T> {
+        Polygon { points }
+    }
+}
+
+impl<T> Polygon<T>
+where
+    T: Num,
+{
+    /// Returns all edges, the last one goes from the last vertex back to the first one.
+    pub fn edges(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        let n = self.points.len();
+        (0..n).map(move |i| Segment::new(self.points[i].clone(), self.points[(i + 1) % n].clone()))
+    }
+    /// Calculate twice the signed area with the shoelace formula. It is positive if the vertices
+    /// are in counter-clockwise order and exact for integers, as it is computed in the wide type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let mut poly = Polygon::new(vec![Point2D::new(0i64, 0), Point2D::new(1, 0), Point2D::new(0, 1)]);
+    /// assert!(poly.area2() == 1);
+    /// assert!(poly.area() == 0.5);
+    /// poly.points.reverse();
+    /// assert!(poly.area2() == -1);
+    /// ```
+    pub fn area2(&self) -> T::Wide {
+        self.edges().fold(T::Wide::zero(), |acc, e| {
+            acc + e.a.widen().cross(e.b.widen())
+        })
+    }
+    /// Calculate the (unsigned) area.
+    pub fn area(&self) -> f64 {
+        self.area2().abs().to_f64() / 2.0
+    }
+    /// Calculate the length of the boundary.
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|e| e.len()).sum()
+    }
+    /// Returns true if the vertices are in counter-clockwise order.
+    pub fn is_ccw(&self) -> bool {
+        self.area2() > T::Wide::zero()
+    }
+    /// Reverse the vertices if needed, so that they are in counter-clockwise order.
+    pub fn make_ccw(&mut self) {
+        if self.area2() < T::Wide::zero() {
+            self.points.reverse();
+        }
+    }
+    /// Reverse the vertices if needed, so that they are in clockwise order.
+    pub fn make_cw(&mut self) {
+        if self.area2() > T::Wide::zero() {
+            self.points.reverse();
+        }
+    }
+    /// Count the lattice points on the boundary (vertices included). Only works for integer
+    /// coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let poly = Polygon::new(vec![Point2D::new(0i64, 0), Point2D::new(4, 0), Point2D::new(0, 2)]);
+    /// assert!(poly.boundary_points() == 8);
+    /// ```
+    pub fn boundary_points(&self) -> T::Wide {
+        self.edges().fold(T::Wide::zero(), |acc, e| {
+            let d = e.b.widen() - e.a.widen();
+            acc + gcd(d.x, d.y)
+        })
+    }
+    /// Count the lattice points strictly inside with Pick's theorem (A = I + B / 2 - 1). Only
+    /// works for integer coordinates, e.g. to find the area enclosed by a path on a grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// // a path along the cells (0, 0) -> (3, 0) -> (3, 2) -> (0, 2)
+    /// let poly = Polygon::new(vec![
+    ///     Point2D::new(0i64, 0),
+    ///     Point2D::new(3, 0),
+    ///     Point2D::new(3, 2),
+    ///     Point2D::new(0, 2),
+    /// ]);
+    /// assert!(poly.interior_points() == 2);
+    /// // all cells on or inside the path
+    /// assert!(poly.interior_points() + poly.boundary_points() == 12);
+    /// ```
+    pub fn interior_points(&self) -> T::Wide {
+        let two = T::Wide::one() + T::Wide::one();
+        (self.area2().abs() - self.boundary_points() + two.clone()) / two
+    }
+    /// Returns true if p lies on an edge.
+    fn on_boundary(&self, p: &Point2D<T>) -> bool {
+        self.edges().any(|e| e.contains(p))
+    }
+    /// Calculate how often the boundary winds counter-clockwise around p (negative for clockwise
+    /// polygons). The result is meaningless for points on the boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let poly = Polygon::new(vec![Point2D::new(0i64, 0), Point2D::new(4, 0), Point2D::new(0, 4)]);
+    /// assert!(poly.winding_number(&Point2D::new(1, 1)) == 1);
+    /// assert!(poly.winding_number(&Point2D::new(3, 3)) == 0);
+    /// let square = Polygon::new(vec![
+    ///     Point2D::new(0i64, 0),
+    ///     Point2D::new(0, 2),
+    ///     Point2D::new(2, 2),
+    ///     Point2D::new(2, 0),
+    /// ]);
+    /// assert!(square.winding_number(&Point2D::new(1, 1)) == -1);
+    /// ```
+    pub fn winding_number(&self, p: &Point2D<T>) -> i64 {
+        let mut winding = 0;
+        for e in self.edges() {
+            if e.a.y <= p.y {
+                if e.b.y > p.y && orient(&e.a, &e.b, p) == Orientation::Left {
+                    winding += 1;
+                }
+            } else if e.b.y <= p.y && orient(&e.a, &e.b, p) == Orientation::Right {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+    /// Find out if p lies inside, outside or on the boundary of the polygon with the winding
+    /// number (nonzero rule). This is exact for integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let poly = Polygon::new(vec![
+    ///     Point2D::new(0i64, 0),
+    ///     Point2D::new(4, 0),
+    ///     Point2D::new(4, 4),
+    ///     Point2D::new(2, 1),
+    ///     Point2D::new(0, 4),
+    /// ]);
+    /// assert!(poly.locate(&Point2D::new(1, 1)) == PointLocation::Inside);
+    /// assert!(poly.locate(&Point2D::new(2, 3)) == PointLocation::Outside);
+    /// assert!(poly.locate(&Point2D::new(3, 2)) == PointLocation::Inside);
+    /// assert!(poly.locate(&Point2D::new(2, 0)) == PointLocation::OnBoundary);
+    /// assert!(poly.locate(&Point2D::new(4, 1)) == PointLocation::OnBoundary);
+    /// ```
+    pub fn locate(&self, p: &Point2D<T>) -> PointLocation {
+        if self.on_boundary(p) {
+            PointLocation::OnBoundary
+        } else if self.winding_number(p) != 0 {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+    /// Find out if p lies inside, outside or on the boundary of the polygon by casting a ray to
+    /// the right and counting the crossed edges (even-odd rule). For simple polygons this is the
+    /// same as [`Polygon::locate`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let poly = Polygon::new(vec![Point2D::new(0i64, 0), Point2D::new(4, 0), Point2D::new(0, 4)]);
+    /// assert!(poly.locate_ray(&Point2D::new(1, 1)) == PointLocation::Inside);
+    /// assert!(poly.locate_ray(&Point2D::new(2, 2)) == PointLocation::OnBoundary);
+    /// assert!(poly.locate_ray(&Point2D::new(3, 3)) == PointLocation::Outside);
+    /// ```
+    pub fn locate_ray(&self, p: &Point2D<T>) -> PointLocation {
+        if self.on_boundary(p) {
+            return PointLocation::OnBoundary;
+        }
+        let mut inside = false;
+        for e in self.edges() {
+            // half-open in y, so that vertices on the ray are counted once
+            if (e.a.y > p.y) != (e.b.y > p.y) {
+                let (lo, hi) = if e.a.y < e.b.y {
+                    (&e.a, &e.b)
+                } else {
+                    (&e.b, &e.a)
+                };
+                if orient(lo, hi, p) == Orientation::Left {
+                    inside = !inside;
+                }
+            }
+        }
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+    /// Returns true if the polygon is convex, collinear vertices are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let p = |x: i64, y: i64| Point2D::new(x, y);
+    /// assert!(Polygon::new(vec![p(0, 0), p(2, 0), p(4, 0), p(4, 4), p(0, 4)]).is_convex());
+    /// assert!(!Polygon::new(vec![p(0, 0), p(4, 0), p(1, 1), p(0, 4)]).is_convex());
+    /// ```
+    pub fn is_convex(&self) -> bool {
+        let n = self.points.len();
+        let (mut left, mut right) = (false, false);
+        for i in 0..n {
+            let (a, b, c) = (
+                &self.points[i],
+                &self.points[(i + 1) % n],
+                &self.points[(i + 2) % n],
+            );
+            match orient(a, b, c) {
+                Orientation::Left => left = true,
+                Orientation::Right => right = true,
+                Orientation::Collinear => {}
+            }
+        }
+        !(left && right)
+    }
+}
@@ -0,0 +1,263 @@
+use super::{Grid, Point2D, Polygon};
+use std::collections::HashSet;
+
+/// Compute the grid points on the line from a to b (both included) with Bresenham's algorithm.
+/// Consecutive points are 8-connected and there are max(|dx|, |dy|) + 1 of them.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let line = bresenham(&Point2D::new(0, 0), &Point2D::new(5, -2));
+/// let expected = [(0, 0), (1, 0), (2, -1), (3, -1), (4, -2), (5, -2)];
+/// let expected = expected.map(|(x, y)| Point2D::new(x, y));
+/// assert!(line == expected);
+/// ```
+pub fn bresenham(a: &Point2D<i64>, b: &Point2D<i64>) -> Vec<Point2D<i64>> {
+    let (dx, dy) = ((b.x - a.x).abs(), -(b.y - a.y).abs());
+    let (sx, sy) = ((b.x - a.x).signum(), (b.y - a.y).signum());
+    let mut err = dx + dy;
+    let mut p = *a;
+    let mut points = vec![p];
+    while p != *b {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            p.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            p.y += sy;
+        }
+        points.push(p);
+    }
+    points
+}
+
+/// Returns the grid points from a to b (both included) if the line is horizontal, vertical or
+/// diagonal at 45 degrees, so that it passes exactly through them. Otherwise None.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+/// use std::collections::HashMap;
+///
+/// // AoC 2021 day 5: count the points covered by at least two vent lines
+/// let vents = [((0, 9), (5, 9)), ((8, 0), (0, 8)), ((9, 4), (3, 4)), ((2, 2), (2, 1)),
+///     ((7, 0), (7, 4)), ((6, 4), (2, 0)), ((0, 9), (2, 9)), ((3, 4), (1, 4)), ((0, 0), (8, 8)),
+///     ((5, 5), (8, 2))];
+/// let mut count = HashMap::new();
+/// for ((x1, y1), (x2, y2)) in vents {
+///     for p in straight_line(&Point2D::new(x1, y1), &Point2D::new(x2, y2)).unwrap() {
+///         *count.entry(p).or_insert(0) += 1;
+///     }
+/// }
+/// assert!(count.values().filter(|&&c| c >= 2).count() == 12);
+/// assert!(straight_line(&Point2D::new(0, 0), &Point2D::new(2, 1)).is_none());
+/// ```
+pub fn straight_line(a: &Point2D<i64>, b: &Point2D<i64>) -> Option<Vec<Point2D<i64>>> {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
+        return None;
+    }
+    let step = Point2D::new(dx.signum(), dy.signum());
+    let n = dx.abs().max(dy.abs());
+    Some((0..=n).map(|i| *a + step * i).collect())
+}
+
+/// Fill a polygon with integer vertices: returns the grid points inside or on its boundary as
+/// inclusive ranges (y, x1, x2) per row, sorted by y. Each row is computed exactly from the
+/// crossings of the edges in O(n log n), so this takes O(h n log n) for height h.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let p = |x: i64, y: i64| Point2D::new(x, y);
+/// let triangle = Polygon::new(vec![p(0, 0), p(4, 0), p(0, 4)]);
+/// let rows = fill_polygon(&triangle);
+/// assert!(rows == vec![(0, 0, 4), (1, 0, 3), (2, 0, 2), (3, 0, 1), (4, 0, 0)]);
+/// let points = rows.iter().map(|r| r.2 - r.1 + 1).sum::<i64>();
+/// // Pick's theorem: area = interior + boundary / 2 - 1
+/// assert!(points == 3 + 12);
+/// ```
+pub fn fill_polygon(polygon: &Polygon<i64>) -> Vec<(i64, i64, i64)> {
+    let points = &polygon.points;
+    let (Some(lo), Some(hi)) = (
+        points.iter().map(|p| p.y).min(),
+        points.iter().map(|p| p.y).max(),
+    ) else {
+        return vec![];
+    };
+    let floor = |num: i128, den: i128| num.div_euclid(den) as i64;
+    let mut rows = vec![];
+    for y in lo..=hi {
+        let mut crossings = vec![];
+        let mut ranges = vec![];
+        for (i, a) in points.iter().enumerate() {
+            let b = &points[(i + 1) % points.len()];
+            let (a, b) = if a.y <= b.y { (a, b) } else { (b, a) };
+            if y < a.y || b.y < y {
+                continue;
+            }
+            if a.y == b.y {
+                ranges.push((a.x.min(b.x), a.x.max(b.x)));
+                continue;
+            }
+            // x = num / den exactly, with den > 0
+            let den = (b.y - a.y) as i128;
+            let num = a.x as i128 * den + (y - a.y) as i128 * (b.x - a.x) as i128;
+            if num % den == 0 {
+                ranges.push((floor(num, den), floor(num, den)));
+            }
+            // edges count as crossing on their lower end only, so vertices are counted once
+            if y < b.y {
+                crossings.push((num, den));
+            }
+        }
+        crossings.sort_by(|a, b| (a.0 * b.1).cmp(&(b.0 * a.1)));
+        for pair in crossings.chunks(2) {
+            if let [(n1, d1), (n2, d2)] = pair {
+                let x1 = -floor(-n1, *d1);
+                let x2 = floor(*n2, *d2);
+                if x1 <= x2 {
+                    ranges.push((x1, x2));
+                }
+            }
+        }
+        ranges.sort();
+        for (x1, x2) in ranges {
+            match rows.last_mut() {
+                Some((ly, _, lx2)) if *ly == y && x1 <= *lx2 + 1 => *lx2 = x2.max(*lx2),
+                _ => rows.push((y, x1, x2)),
+            }
+        }
+    }
+    rows
+}
+
+/// A connected set of grid cells, see [`flood_fill`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The cells in the order in which they were reached.
+    pub cells: Vec<Point2D<i64>>,
+    /// The number of cell sides between a cell of the region and one outside of it.
+    pub perimeter: usize,
+}
+
+impl Region {
+    /// The number of cells.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Find the region of cells reachable from start (which must satisfy inside) with a breadth-first
+/// search. Cells are connected to their 4 orthogonal neighbours, or to all 8 if diagonal is true.
+/// The perimeter always counts orthogonal sides.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let grid: Grid<char> = "##.\n..#\n#.#".parse().unwrap();
+/// let open = |p: &Point2D<i64>| grid.get(p) == Some(&'.');
+/// let region = flood_fill(Point2D::new(0, 1), false, open);
+/// assert!(region.area() == 3 && region.perimeter == 8);
+/// // the cell in the top right corner is only connected diagonally
+/// assert!(flood_fill(Point2D::new(0, 1), true, open).area() == 4);
+/// ```
+pub fn flood_fill<F>(start: Point2D<i64>, diagonal: bool, mut inside: F) -> Region
+where
+    F: FnMut(&Point2D<i64>) -> bool,
+{
+    let mut seen = HashSet::from([start]);
+    let mut cells = vec![start];
+    let mut perimeter = 0;
+    let mut i = 0;
+    while i < cells.len() {
+        let p = cells[i];
+        i += 1;
+        for q in p.neighbors4() {
+            if !inside(&q) {
+                perimeter += 1;
+            } else if seen.insert(q) {
+                cells.push(q);
+            }
+        }
+        if diagonal {
+            for q in p.neighbors8() {
+                if q.x != p.x && q.y != p.y && inside(&q) && seen.insert(q) {
+                    cells.push(q);
+                }
+            }
+        }
+    }
+    Region { cells, perimeter }
+}
+
+/// Split a set of cells into its connected regions, see [`flood_fill`].
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+/// use std::collections::HashSet;
+///
+/// let cells = HashSet::from([(0, 0), (1, 0), (2, 1), (5, 5)].map(|(x, y)| Point2D::new(x, y)));
+/// let mut areas = regions(&cells, false).iter().map(|r| r.area()).collect::<Vec<_>>();
+/// areas.sort();
+/// assert!(areas == vec![1, 1, 2]);
+/// assert!(regions(&cells, true).len() == 2);
+/// ```
+pub fn regions(cells: &HashSet<Point2D<i64>>, diagonal: bool) -> Vec<Region> {
+    let mut seen = HashSet::new();
+    let mut result = vec![];
+    for p in cells {
+        if !seen.contains(p) {
+            let region = flood_fill(*p, diagonal, |q| cells.contains(q));
+            seen.extend(region.cells.iter().copied());
+            result.push(region);
+        }
+    }
+    result
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    /// Split the grid into connected regions of equal cells in row-major order of their first
+    /// cell, see [`flood_fill`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// // AoC 2024 day 12: the price of a region is its area times its perimeter
+    /// let garden: Grid<char> = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+    /// let regions = garden.regions(false);
+    /// assert!(regions.len() == 5 && garden[regions[1].cells[0]] == 'B');
+    /// assert!(regions.iter().map(|r| r.area() * r.perimeter).sum::<usize>() == 140);
+    /// ```
+    pub fn regions(&self, diagonal: bool) -> Vec<Region> {
+        let mut seen = Grid::new(self.width, self.height, false);
+        let mut result = vec![];
+        for p in self.points() {
+            if !seen[p] {
+                let value = &self[p];
+                let region = flood_fill(p, diagonal, |q| self.get(q) == Some(value));
+                for q in &region.cells {
+                    seen[*q] = true;
+                }
+                result.push(region);
+            }
+        }
+        result
+    }
+}
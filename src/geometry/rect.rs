@@ -0,0 +1,204 @@
+use super::{Point2D, Point3D};
+use crate::num::{Num, Scalar};
+
+/// The lower and upper corner of a box with any number of dimensions.
+type Bounds<T> = (Vec<T>, Vec<T>);
+
+fn is_empty<T: Num>(b: &Bounds<T>) -> bool {
+    b.0.iter().zip(&b.1).any(|(lo, hi)| lo >= hi)
+}
+
+fn measure<T: Num>(b: &Bounds<T>) -> T::Wide {
+    if is_empty(b) {
+        return T::Wide::zero();
+    }
+    b.0.iter().zip(&b.1).fold(T::Wide::one(), |acc, (lo, hi)| {
+        acc * (hi.widen() - lo.widen())
+    })
+}
+
+fn intersect<T: Num>(a: &Bounds<T>, b: &Bounds<T>) -> Option<Bounds<T>> {
+    let max = |x: &T, y: &T| if x > y { x.clone() } else { y.clone() };
+    let min = |x: &T, y: &T| if x < y { x.clone() } else { y.clone() };
+    let lo = a.0.iter().zip(&b.0).map(|(x, y)| max(x, y)).collect();
+    let hi = a.1.iter().zip(&b.1).map(|(x, y)| min(x, y)).collect();
+    let i = (lo, hi);
+    (!is_empty(&i)).then_some(i)
+}
+
+/// Cut a into disjoint boxes (at most two per dimension) which cover a without b.
+fn subtract<T: Num>(a: &Bounds<T>, b: &Bounds<T>) -> Vec<Bounds<T>> {
+    if is_empty(a) {
+        return vec![];
+    }
+    let Some(cut) = intersect(a, b) else {
+        return vec![a.clone()];
+    };
+    let mut rest = a.clone();
+    let mut pieces = vec![];
+    for d in 0..rest.0.len() {
+        if rest.0[d] < cut.0[d] {
+            let mut piece = rest.clone();
+            piece.1[d] = cut.0[d].clone();
+            pieces.push(piece);
+            rest.0[d] = cut.0[d].clone();
+        }
+        if cut.1[d] < rest.1[d] {
+            let mut piece = rest.clone();
+            piece.0[d] = cut.1[d].clone();
+            pieces.push(piece);
+            rest.1[d] = cut.1[d].clone();
+        }
+    }
+    pieces
+}
+
+/// An axis-aligned rectangle containing the points p with lo <= p < hi in both coordinates. For
+/// inclusive integer ranges use hi + 1. Areas are computed in the wide type of T, i.e. in
+/// `i128` for `i64` coordinates.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let a = Rect::new(Point2D::new(0i64, 0), Point2D::new(4, 3));
+/// let b = Rect::new(Point2D::new(2, 1), Point2D::new(6, 5));
+/// assert!(a.area() == 12 && a.contains(&Point2D::new(3, 2)) && !a.contains(&Point2D::new(4, 2)));
+/// assert!(a.intersect(&b) == Some(Rect::new(Point2D::new(2, 1), Point2D::new(4, 3))));
+/// let pieces = a.subtract(&b);
+/// assert!(pieces.iter().map(|r| r.area()).sum::<i128>() == 8);
+/// assert!(Rect::union_area(&[a, b]) == 12 + 16 - 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub lo: Point2D<T>,
+    pub hi: Point2D<T>,
+}
+
+impl<T> Rect<T>
+where
+    T: Num,
+{
+    /// Create a new rectangle from its lower (inclusive) and upper (exclusive) corner.
+    pub fn new(lo: Point2D<T>, hi: Point2D<T>) -> Rect<T> {
+        Rect { lo, hi }
+    }
+    fn bounds(&self) -> Bounds<T> {
+        (
+            vec![self.lo.x.clone(), self.lo.y.clone()],
+            vec![self.hi.x.clone(), self.hi.y.clone()],
+        )
+    }
+    fn from_bounds(b: Bounds<T>) -> Rect<T> {
+        let [[lx, ly], [hx, hy]] = [b.0, b.1].map(|v| <[T; 2]>::try_from(v).unwrap());
+        Rect::new(Point2D::new(lx, ly), Point2D::new(hx, hy))
+    }
+    /// Returns true if the rectangle contains no points.
+    pub fn is_empty(&self) -> bool {
+        is_empty(&self.bounds())
+    }
+    /// Calculate the area, it is zero for empty rectangles.
+    pub fn area(&self) -> T::Wide {
+        measure(&self.bounds())
+    }
+    /// Returns true if p lies inside the rectangle.
+    pub fn contains(&self, p: &Point2D<T>) -> bool {
+        self.lo.x <= p.x && p.x < self.hi.x && self.lo.y <= p.y && p.y < self.hi.y
+    }
+    /// Returns true if other lies inside the rectangle (empty rectangles lie in every one).
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        other.is_empty() || self.intersect(other).as_ref() == Some(other)
+    }
+    /// Returns the intersection, or None if it is empty.
+    pub fn intersect(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        intersect(&self.bounds(), &other.bounds()).map(Rect::from_bounds)
+    }
+    /// Returns at most four disjoint rectangles covering self without other.
+    pub fn subtract(&self, other: &Rect<T>) -> Vec<Rect<T>> {
+        subtract(&self.bounds(), &other.bounds())
+            .into_iter()
+            .map(Rect::from_bounds)
+            .collect()
+    }
+}
+
+/// An axis-aligned cuboid containing the points p with lo <= p < hi in all coordinates. For
+/// inclusive integer ranges use hi + 1. Volumes are computed in the wide type of T, i.e. in
+/// `i128` for `i64` coordinates.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// // AoC 2021 day 22: keep the cubes which are on as disjoint cuboids
+/// let steps = [
+///     (true, Cuboid::new(Point3D::new(10, 10, 10), Point3D::new(13, 13, 13))),
+///     (true, Cuboid::new(Point3D::new(11, 11, 11), Point3D::new(14, 14, 14))),
+///     (false, Cuboid::new(Point3D::new(9, 9, 9), Point3D::new(12, 12, 12))),
+///     (true, Cuboid::new(Point3D::new(10, 10, 10), Point3D::new(11, 11, 11))),
+/// ];
+/// let mut on: Vec<Cuboid<i64>> = vec![];
+/// for (turn_on, c) in &steps {
+///     on = on.iter().flat_map(|o| o.subtract(c)).collect();
+///     if *turn_on {
+///         on.push(*c);
+///     }
+/// }
+/// assert!(on.iter().map(|c| c.volume()).sum::<i128>() == 39);
+///
+/// let all = steps.iter().map(|s| s.1).collect::<Vec<_>>();
+/// assert!(Cuboid::union_volume(&all) == 27 + 27 + 27 - 8 - 8 - 1 + 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T> {
+    pub lo: Point3D<T>,
+    pub hi: Point3D<T>,
+}
+
+impl<T> Cuboid<T>
+where
+    T: Num,
+{
+    /// Create a new cuboid from its lower (inclusive) and upper (exclusive) corner.
+    pub fn new(lo: Point3D<T>, hi: Point3D<T>) -> Cuboid<T> {
+        Cuboid { lo, hi }
+    }
+    fn bounds(&self) -> Bounds<T> {
+        let (lo, hi) = (self.lo.clone(), self.hi.clone());
+        (vec![lo.x, lo.y, lo.z], vec![hi.x, hi.y, hi.z])
+    }
+    fn from_bounds(b: Bounds<T>) -> Cuboid<T> {
+        let [[lx, ly, lz], [hx, hy, hz]] = [b.0, b.1].map(|v| <[T; 3]>::try_from(v).unwrap());
+        Cuboid::new(Point3D::new(lx, ly, lz), Point3D::new(hx, hy, hz))
+    }
+    /// Returns true if the cuboid contains no points.
+    pub fn is_empty(&self) -> bool {
+        is_empty(&self.bounds())
+    }
+    /// Calculate the volume, it is zero for empty cuboids.
+    pub fn volume(&self) -> T::Wide {
+        measure(&self.bounds())
+    }
+    /// Returns true if p lies inside the cuboid.
+    pub fn contains(&self, p: &Point3D<T>) -> bool {
+        let (lo, hi) = (&self.lo, &self.hi);
+        lo.x <= p.x && p.x < hi.x && lo.y <= p.y && p.y < hi.y && lo.z <= p.z && p.z < hi.z
+    }
+    /// Returns true if other lies inside the cuboid (empty cuboids lie in every one).
+    pub fn contains_cuboid(&self, other: &Cuboid<T>) -> bool {
+        other.is_empty() || self.intersect(other).as_ref() == Some(other)
+    }
+    /// Returns the intersection, or None if it is empty.
+    pub fn intersect(&self, other: &Cuboid<T>) -> Option<Cuboid<T>> {
+        intersect(&self.bounds(), &other.bounds()).map(Cuboid::from_bounds)
+    }
+    /// Returns at most six disjoint cuboids covering self without other.
+    pub fn subtract(&self, other: &Cuboid<T>) -> Vec<Cuboid<T>> {
+        subtract(&self.bounds(), &other.bounds())
+            .into_iter()
+            .map(Cuboid::from_bounds)
+            .collect()
+    }
+}
@@ -0,0 +1,154 @@
+use super::Point3D;
+use crate::num::Num;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// One of the 24 rotations mapping the coordinate axes onto each other, as an integer matrix
+/// with one entry 1 or -1 in every row and column (and determinant 1).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// // rotate by 90 degrees counter-clockwise around the z axis
+/// let rot = Rotation::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+/// assert!(rot.apply(&Point3D::new(1, 2, 3)) == Point3D::new(-2, 1, 3));
+/// assert!(rot.compose(&rot.inverse()) == Rotation::IDENTITY);
+/// let half = rot.compose(&rot);
+/// assert!(half.apply(&Point3D::new(1, 2, 3)) == Point3D::new(-1, -2, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rotation {
+    pub m: [[i64; 3]; 3],
+}
+
+impl Rotation {
+    /// The rotation which doesn't change anything.
+    pub const IDENTITY: Rotation = Rotation {
+        m: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// Create a rotation from its matrix. Panics if it isn't one of the 24 rotations.
+    pub fn new(m: [[i64; 3]; 3]) -> Rotation {
+        let rot = Rotation { m };
+        assert!(
+            Rotation::all().contains(&rot),
+            "the matrix is not an axis-aligned rotation"
+        );
+        rot
+    }
+    /// Returns all 24 rotations, starting with the identity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let all = Rotation::all();
+    /// assert!(all[0] == Rotation::IDENTITY);
+    /// let p = Point3D::new(1, 2, 3);
+    /// let mut images = all.iter().map(|r| r.apply(&p)).collect::<Vec<_>>();
+    /// images.sort();
+    /// images.dedup();
+    /// assert!(images.len() == 24);
+    /// ```
+    pub fn all() -> Vec<Rotation> {
+        const PERMS: [([usize; 3], i64); 6] = [
+            ([0, 1, 2], 1),
+            ([0, 2, 1], -1),
+            ([1, 0, 2], -1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([2, 1, 0], -1),
+        ];
+        let mut all = vec![];
+        for (perm, parity) in PERMS {
+            for signs in 0..8 {
+                let sign = |i: usize| if signs >> i & 1 == 0 { 1 } else { -1 };
+                if sign(0) * sign(1) * sign(2) != parity {
+                    continue;
+                }
+                let mut m = [[0; 3]; 3];
+                for i in 0..3 {
+                    m[i][perm[i]] = sign(i);
+                }
+                all.push(Rotation { m });
+            }
+        }
+        all
+    }
+    /// Rotate a point.
+    pub fn apply<T: Num>(&self, p: &Point3D<T>) -> Point3D<T> {
+        let c = [&p.x, &p.y, &p.z];
+        let row = |r: &[i64; 3]| {
+            (0..3).fold(T::zero(), |acc, j| match r[j] {
+                1 => acc + c[j].clone(),
+                -1 => acc - c[j].clone(),
+                _ => acc,
+            })
+        };
+        Point3D::new(row(&self.m[0]), row(&self.m[1]), row(&self.m[2]))
+    }
+    /// Returns the rotation which first applies other and then self.
+    pub fn compose(&self, other: &Rotation) -> Rotation {
+        let mut m = [[0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = (0..3).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        Rotation { m }
+    }
+    /// Returns the rotation which undoes self.
+    pub fn inverse(&self) -> Rotation {
+        let mut m = [[0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = self.m[j][i];
+            }
+        }
+        Rotation { m }
+    }
+}
+
+/// Find a rotation and translation which map at least k points of b onto points of a, i.e.
+/// `rot.apply(p) + shift` lies in a for k points p of b. The points in each set have to be
+/// distinct. This counts the translations of all pairs for all 24 rotations in O(24 n m).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let a = vec![Point3D::new(0, 0, 0), Point3D::new(1, 2, 3), Point3D::new(5, -1, 2), Point3D::new(9, 9, 9)];
+/// let rot = Rotation::all()[13];
+/// let shift = Point3D::new(10, -20, 30);
+/// // b sees the first three points of a from another position and orientation
+/// let b = a[..3]
+///     .iter()
+///     .map(|p| rot.inverse().apply(&(*p - shift)))
+///     .chain([Point3D::new(100, 100, 100)])
+///     .collect::<Vec<_>>();
+/// assert!(align(&a, &b, 3) == Some((rot, shift)));
+/// assert!(align(&a, &b, 4).is_none());
+/// ```
+pub fn align<T>(a: &[Point3D<T>], b: &[Point3D<T>], k: usize) -> Option<(Rotation, Point3D<T>)>
+where
+    T: Num + Eq + Hash,
+{
+    for rot in Rotation::all() {
+        let mut votes: HashMap<Point3D<T>, usize> = HashMap::new();
+        for q in b {
+            let q = rot.apply(q);
+            for p in a {
+                let count = votes.entry(p.clone() - q.clone()).or_insert(0);
+                *count += 1;
+                if *count >= k {
+                    return Some((rot, p.clone() - q));
+                }
+            }
+        }
+    }
+    None
+}
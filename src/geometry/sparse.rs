@@ -0,0 +1,252 @@
+use super::{Grid, Point2D, Rect};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Index;
+
+/// An unbounded grid which only stores the cells that differ from a default value, for
+/// simulations which grow in every direction. The bounding box of the stored cells is tracked,
+/// it grows when cells are set and only shrinks with [`SparseGrid::fit_bounds`]. Cells are
+/// changed with [`SparseGrid::set`] or [`SparseGrid::update`], so that cells with the default
+/// value are never stored. Like [`Grid`], it is printed with y growing downwards.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// // Game of Life: a blinker flips between horizontal and vertical
+/// let mut life = SparseGrid::from_grid(&"...\n###\n...".parse().unwrap(), '.');
+/// for _ in 0..3 {
+///     let mut next = SparseGrid::new('.');
+///     let bounds = life.bounds().unwrap();
+///     for y in bounds.lo.y - 1..=bounds.hi.y {
+///         for x in bounds.lo.x - 1..=bounds.hi.x {
+///             let p = Point2D::new(x, y);
+///             let alive = life.neighbors8(&p).filter(|(_, &c)| c == '#').count();
+///             if alive == 3 || (alive == 2 && life[p] == '#') {
+///                 next.set(p, '#');
+///             }
+///         }
+///     }
+///     life = next;
+/// }
+/// assert!(life.len() == 3 && life.to_string() == "#\n#\n#");
+/// assert!(life.bounds() == Some(Rect::new(Point2D::new(1, 0), Point2D::new(2, 3))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2D<i64>, T>,
+    default: T,
+    /// The smallest and largest coordinates of the stored cells.
+    bounds: Option<(Point2D<i64>, Point2D<i64>)>,
+}
+
+impl<T> SparseGrid<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Create an empty grid, every cell has the default value.
+    pub fn new(default: T) -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+    /// Store the cells of a dense grid which differ from default, cell (x, y) of the grid is
+    /// stored at (x, y).
+    pub fn from_grid(grid: &Grid<T>, default: T) -> SparseGrid<T> {
+        let mut sparse = SparseGrid::new(default);
+        for (p, value) in grid.points().zip(&grid.cells) {
+            sparse.set(p, value.clone());
+        }
+        sparse
+    }
+    /// Convert the cells inside the bounding box into a dense grid. Returns it together with the
+    /// point of the sparse grid which is stored at (0, 0).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let mut sparse = SparseGrid::new(0);
+    /// sparse.set(Point2D::new(-2, 5), 1);
+    /// sparse.set(Point2D::new(0, 4), 2);
+    /// let (grid, offset) = sparse.to_grid();
+    /// assert!(offset == Point2D::new(-2, 4));
+    /// assert!(grid == Grid::from_rows(vec![vec![0, 0, 2], vec![1, 0, 0]]));
+    /// assert!(SparseGrid::from_grid(&grid, 0).len() == 2);
+    /// ```
+    pub fn to_grid(&self) -> (Grid<T>, Point2D<i64>) {
+        let Some(bounds) = self.bounds() else {
+            return (Grid::new(0, 0, self.default.clone()), Point2D::new(0, 0));
+        };
+        let size = bounds.hi - bounds.lo;
+        let mut grid = Grid::new(size.x as usize, size.y as usize, self.default.clone());
+        for (p, value) in &self.cells {
+            grid[*p - bounds.lo] = value.clone();
+        }
+        (grid, bounds.lo)
+    }
+    /// The value of cells which are not stored.
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+    /// Returns the cell at p.
+    pub fn get(&self, p: &Point2D<i64>) -> &T {
+        self.cells.get(p).unwrap_or(&self.default)
+    }
+    /// Change the cell at p in place, it is removed if it ends up with the default value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let mut counts = SparseGrid::new(0);
+    /// let p = Point2D::new(3, -1);
+    /// counts.update(p, |c| *c += 2);
+    /// assert!(counts[p] == 2 && counts.len() == 1);
+    /// counts.update(p, |c| *c -= 2);
+    /// assert!(counts.is_empty() && counts == SparseGrid::new(0));
+    /// ```
+    pub fn update<F>(&mut self, p: Point2D<i64>, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.get(&p).clone();
+        f(&mut value);
+        self.set(p, value);
+    }
+    /// Set the cell at p, setting it to the default value removes it.
+    pub fn set(&mut self, p: Point2D<i64>, value: T) {
+        if value == self.default {
+            self.cells.remove(&p);
+        } else {
+            self.grow(&p);
+            self.cells.insert(p, value);
+        }
+    }
+    /// Remove the cell at p, so that it has the default value again. The bounding box is not
+    /// shrunk, see [`SparseGrid::fit_bounds`].
+    pub fn remove(&mut self, p: &Point2D<i64>) -> Option<T> {
+        self.cells.remove(p)
+    }
+    fn grow(&mut self, p: &Point2D<i64>) {
+        let (lo, hi) = self.bounds.get_or_insert((*p, *p));
+        *lo = Point2D::new(lo.x.min(p.x), lo.y.min(p.y));
+        *hi = Point2D::new(hi.x.max(p.x), hi.y.max(p.y));
+    }
+    /// The number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    /// Returns true if no cells are stored.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    /// Iterate over the stored cells in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Point2D<i64>, &T)> {
+        self.cells.iter()
+    }
+    /// The bounding box of the stored cells (with exclusive upper corner), None if there never
+    /// were any. It may be larger than necessary after cells were removed.
+    pub fn bounds(&self) -> Option<Rect<i64>> {
+        self.bounds
+            .map(|(lo, hi)| Rect::new(lo, hi + Point2D::new(1, 1)))
+    }
+    /// Shrink the bounding box to the stored cells in O(n).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let mut grid = SparseGrid::new('.');
+    /// grid.set(Point2D::new(0, 0), '#');
+    /// grid.set(Point2D::new(2, 1), '#');
+    /// grid.remove(&Point2D::new(2, 1));
+    /// assert!(grid.to_string() == "#..\n...");
+    /// grid.fit_bounds();
+    /// assert!(grid.to_string() == "#");
+    /// ```
+    pub fn fit_bounds(&mut self) {
+        self.bounds = None;
+        let points = self.cells.keys().copied().collect::<Vec<_>>();
+        for p in &points {
+            self.grow(p);
+        }
+    }
+    /// Iterate over the four orthogonal neighbours of p and their cells.
+    pub fn neighbors4(&self, p: &Point2D<i64>) -> impl Iterator<Item = (Point2D<i64>, &T)> {
+        p.neighbors4().map(move |q| (q, self.get(&q)))
+    }
+    /// Iterate over all eight neighbours of p and their cells.
+    pub fn neighbors8(&self, p: &Point2D<i64>) -> impl Iterator<Item = (Point2D<i64>, &T)> {
+        p.neighbors8().map(move |q| (q, self.get(&q)))
+    }
+    /// Render the cells inside region, one row per line with y growing downwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let mut sand = SparseGrid::new('.');
+    /// sand.set(Point2D::new(500, 0), '+');
+    /// sand.set(Point2D::new(499, 1), 'o');
+    /// let region = Rect::new(Point2D::new(498, 0), Point2D::new(502, 2));
+    /// assert!(sand.render(&region) == "..+.\n.o..");
+    /// ```
+    pub fn render(&self, region: &Rect<i64>) -> String
+    where
+        T: Display,
+    {
+        let mut rows = vec![];
+        for y in region.lo.y..region.hi.y {
+            let row = (region.lo.x..region.hi.x)
+                .map(|x| self.get(&Point2D::new(x, y)).to_string())
+                .collect::<String>();
+            rows.push(row);
+        }
+        rows.join("\n")
+    }
+}
+
+impl<T> Index<Point2D<i64>> for SparseGrid<T>
+where
+    T: Clone + PartialEq,
+{
+    type Output = T;
+
+    fn index(&self, p: Point2D<i64>) -> &T {
+        self.get(&p)
+    }
+}
+
+/// Grids are equal if they have the same default and store the same cells, their bounding boxes
+/// may differ.
+impl<T> PartialEq for SparseGrid<T>
+where
+    T: Clone + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default && self.cells == other.cells
+    }
+}
+
+impl<T> Eq for SparseGrid<T> where T: Clone + Eq {}
+
+impl<T> Display for SparseGrid<T>
+where
+    T: Clone + PartialEq + Display,
+{
+    /// Render the bounding box, see [`SparseGrid::render`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bounds() {
+            Some(bounds) => write!(f, "{}", self.render(&bounds)),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,631 @@
+use super::{Cuboid, Point2D, Rect, Segment};
+use crate::num::{Num, Scalar};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+fn cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).expect("cannot compare NaN coordinates")
+}
+
+/// A segment tree over the intervals between sorted coordinates, which tracks how much of them
+/// is covered by at least one of the added intervals.
+struct CoverTree<'a, T: Num> {
+    ys: &'a [T],
+    count: Vec<i64>,
+    len: Vec<T::Wide>,
+}
+
+impl<'a, T: Num> CoverTree<'a, T> {
+    fn new(ys: &'a [T]) -> CoverTree<'a, T> {
+        let size = 4 * ys.len().max(1);
+        CoverTree {
+            ys,
+            count: vec![0; size],
+            len: vec![T::Wide::zero(); size],
+        }
+    }
+    /// Add d to the cover count of the intervals l..r (indices into ys).
+    fn update(&mut self, node: usize, l: usize, r: usize, ql: usize, qr: usize, d: i64) {
+        if qr <= l || r <= ql {
+            return;
+        }
+        if ql <= l && r <= qr {
+            self.count[node] += d;
+        } else {
+            let mid = (l + r) / 2;
+            self.update(2 * node, l, mid, ql, qr, d);
+            self.update(2 * node + 1, mid, r, ql, qr, d);
+        }
+        self.len[node] = if self.count[node] > 0 {
+            self.ys[r].widen() - self.ys[l].widen()
+        } else if r - l == 1 {
+            T::Wide::zero()
+        } else {
+            self.len[2 * node].clone() + self.len[2 * node + 1].clone()
+        };
+    }
+    fn add(&mut self, lo: &T, hi: &T, d: i64) {
+        let index = |y: &T| self.ys.partition_point(|v| v < y);
+        let (ql, qr) = (index(lo), index(hi));
+        self.update(1, 0, self.ys.len() - 1, ql, qr, d);
+    }
+    fn covered(&self) -> T::Wide {
+        self.len[1].clone()
+    }
+}
+
+/// Sweep over x through rectangles given as (x1, x2, y1, y2). Returns the area of their union
+/// and the length of its vertical edges.
+fn sweep<T: Num>(rects: &[(T, T, T, T)]) -> (T::Wide, T::Wide) {
+    let rects = rects
+        .iter()
+        .filter(|r| r.0 < r.1 && r.2 < r.3)
+        .collect::<Vec<_>>();
+    let mut ys = rects
+        .iter()
+        .flat_map(|r| [r.2.clone(), r.3.clone()])
+        .collect::<Vec<_>>();
+    ys.sort_by(cmp);
+    ys.dedup();
+    let mut events = rects
+        .iter()
+        .flat_map(|r| [(&r.0, 1, &r.2, &r.3), (&r.1, -1, &r.2, &r.3)])
+        .collect::<Vec<_>>();
+    // add before removing at the same x, so that touching edges cancel out
+    events.sort_by(|a, b| cmp(a.0, b.0).then(b.1.cmp(&a.1)));
+    let mut tree = CoverTree::new(&ys);
+    let (mut area, mut edges) = (T::Wide::zero(), T::Wide::zero());
+    for (i, (x, d, y1, y2)) in events.iter().enumerate() {
+        if i > 0 {
+            area = area + tree.covered() * (x.widen() - events[i - 1].0.widen());
+        }
+        let before = tree.covered();
+        tree.add(y1, y2, *d);
+        edges = edges + (tree.covered() - before).abs();
+    }
+    (area, edges)
+}
+
+impl<T> Rect<T>
+where
+    T: Num,
+{
+    fn sweep_tuple(&self, swap: bool) -> (T, T, T, T) {
+        let (lo, hi) = (self.lo.clone(), self.hi.clone());
+        if swap {
+            (lo.y, hi.y, lo.x, hi.x)
+        } else {
+            (lo.x, hi.x, lo.y, hi.y)
+        }
+    }
+    /// Calculate the area covered by any of the rectangles with a sweep line and a segment tree
+    /// in O(n log n).
+    pub fn union_area(rects: &[Rect<T>]) -> T::Wide {
+        let tuples = rects.iter().map(|r| r.sweep_tuple(false));
+        sweep(&tuples.collect::<Vec<_>>()).0
+    }
+    /// Calculate the perimeter of the union of the rectangles (including the boundary of holes)
+    /// with two sweeps in O(n log n).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::cp_rs::geometry::*;
+    ///
+    /// let r = |x1: i64, y1, x2, y2| Rect::new(Point2D::new(x1, y1), Point2D::new(x2, y2));
+    /// // a 3x3 frame with a hole in the middle
+    /// let frame = [r(0, 0, 3, 1), r(0, 2, 3, 3), r(0, 1, 1, 2), r(2, 1, 3, 2)];
+    /// assert!(Rect::union_area(&frame) == 8);
+    /// assert!(Rect::union_perimeter(&frame) == 12 + 4);
+    /// // touching rectangles
+    /// assert!(Rect::union_perimeter(&[r(0, 0, 1, 1), r(1, 0, 2, 1)]) == 6);
+    /// ```
+    pub fn union_perimeter(rects: &[Rect<T>]) -> T::Wide {
+        let vertical = rects.iter().map(|r| r.sweep_tuple(false));
+        let horizontal = rects.iter().map(|r| r.sweep_tuple(true));
+        sweep(&vertical.collect::<Vec<_>>()).1 + sweep(&horizontal.collect::<Vec<_>>()).1
+    }
+}
+
+impl<T> Cuboid<T>
+where
+    T: Num,
+{
+    /// Calculate the volume covered by any of the cuboids in O(n^2 log n): the x coordinates
+    /// are compressed and the area covered in every slab between them is found with the sweep of
+    /// [`Rect::union_area`].
+    pub fn union_volume(cuboids: &[Cuboid<T>]) -> T::Wide {
+        let mut xs = cuboids
+            .iter()
+            .flat_map(|c| [c.lo.x.clone(), c.hi.x.clone()])
+            .collect::<Vec<_>>();
+        xs.sort_by(cmp);
+        xs.dedup();
+        let mut volume = T::Wide::zero();
+        for w in xs.windows(2) {
+            let slab = cuboids
+                .iter()
+                .filter(|c| c.lo.x <= w[0] && w[1] <= c.hi.x)
+                .map(|c| {
+                    (
+                        c.lo.y.clone(),
+                        c.hi.y.clone(),
+                        c.lo.z.clone(),
+                        c.hi.z.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if !slab.is_empty() {
+                volume = volume + (w[1].widen() - w[0].widen()) * sweep(&slab).0;
+            }
+        }
+        volume
+    }
+}
+
+/// Compare a / b with c / d for positive b and d. If the products overflow, the integer parts
+/// are compared and then the inverted remainders, like in a continued fraction.
+fn cmp_fractions<W: Num>(a: &W, b: &W, c: &W, d: &W) -> Ordering {
+    let floor_div = |a: &W, b: &W| {
+        let q = a.clone() / b.clone();
+        let r = a.clone() - q.clone() * b.clone();
+        if r < W::zero() {
+            (q - W::one(), r + b.clone())
+        } else {
+            (q, r)
+        }
+    };
+    let (mut a, mut b, mut c, mut d) = (a.clone(), b.clone(), c.clone(), d.clone());
+    let mut flipped = false;
+    let order = loop {
+        if let (Some(ad), Some(cb)) = (a.checked_mul(&d), c.checked_mul(&b)) {
+            break cmp(&ad, &cb);
+        }
+        let ((qa, ra), (qc, rc)) = (floor_div(&a, &b), floor_div(&c, &d));
+        if qa != qc {
+            break cmp(&qa, &qc);
+        }
+        if ra.is_zero() || rc.is_zero() {
+            break (!ra.is_zero()).cmp(&!rc.is_zero());
+        }
+        // ra / b < rc / d exactly if b / ra > d / rc
+        (a, b, c, d) = (b, ra, d, rc);
+        flipped = !flipped;
+    };
+    if flipped {
+        order.reverse()
+    } else {
+        order
+    }
+}
+
+/// A point where the sweep stops, with the rational coordinates x / d and y / d (d > 0). They
+/// are ordered by x, then by y.
+#[derive(Debug, Clone)]
+struct SweepPoint<W> {
+    x: W,
+    y: W,
+    d: W,
+}
+
+impl<W: Num> Ord for SweepPoint<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_fractions(&self.x, &self.d, &other.x, &other.d)
+            .then_with(|| cmp_fractions(&self.y, &self.d, &other.y, &other.d))
+    }
+}
+
+impl<W: Num> PartialOrd for SweepPoint<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Num> PartialEq for SweepPoint<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Num> Eq for SweepPoint<W> {}
+
+/// The segments which start, end or cross at a sweep point. The crossing ones are only the
+/// neighbours which were found to cross there, not necessarily all of them.
+#[derive(Debug, Clone, Default)]
+struct Event {
+    starts: Vec<usize>,
+    ends: Vec<usize>,
+    crossing: Vec<usize>,
+}
+
+const NIL: usize = usize::MAX;
+
+/// The segments cut by the sweep line ordered from bottom to top, as a treap whose nodes are the
+/// segment indices. The parent links allow to remove a segment and to find its neighbours
+/// without comparing it to the others.
+struct Status {
+    root: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    parent: Vec<usize>,
+    prio: Vec<u64>,
+}
+
+impl Status {
+    fn new(n: usize) -> Status {
+        let mut seed = 0x9e3779b97f4a7c15u64;
+        let prio = (0..n)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            })
+            .collect();
+        Status {
+            root: NIL,
+            left: vec![NIL; n],
+            right: vec![NIL; n],
+            parent: vec![NIL; n],
+            prio,
+        }
+    }
+    fn node(x: usize) -> Option<usize> {
+        if x == NIL {
+            None
+        } else {
+            Some(x)
+        }
+    }
+    fn leftmost(&self, mut x: usize) -> usize {
+        while self.left[x] != NIL {
+            x = self.left[x];
+        }
+        x
+    }
+    fn first(&self) -> Option<usize> {
+        Self::node(self.root).map(|r| self.leftmost(r))
+    }
+    fn next(&self, mut x: usize) -> Option<usize> {
+        if self.right[x] != NIL {
+            return Some(self.leftmost(self.right[x]));
+        }
+        while self.parent[x] != NIL && self.right[self.parent[x]] == x {
+            x = self.parent[x];
+        }
+        Self::node(self.parent[x])
+    }
+    fn prev(&self, mut x: usize) -> Option<usize> {
+        if self.left[x] != NIL {
+            let mut y = self.left[x];
+            while self.right[y] != NIL {
+                y = self.right[y];
+            }
+            return Some(y);
+        }
+        while self.parent[x] != NIL && self.left[self.parent[x]] == x {
+            x = self.parent[x];
+        }
+        Self::node(self.parent[x])
+    }
+    /// Returns the last segment for which below is true, it has to be true for a prefix.
+    fn last_where(&self, mut below: impl FnMut(usize) -> bool) -> Option<usize> {
+        let (mut x, mut last) = (self.root, None);
+        while x != NIL {
+            if below(x) {
+                last = Some(x);
+                x = self.right[x];
+            } else {
+                x = self.left[x];
+            }
+        }
+        last
+    }
+    /// Move x above its parent.
+    fn rotate_up(&mut self, x: usize) {
+        let y = self.parent[x];
+        let g = self.parent[y];
+        if self.left[y] == x {
+            self.left[y] = self.right[x];
+            if self.right[x] != NIL {
+                self.parent[self.right[x]] = y;
+            }
+            self.right[x] = y;
+        } else {
+            self.right[y] = self.left[x];
+            if self.left[x] != NIL {
+                self.parent[self.left[x]] = y;
+            }
+            self.left[x] = y;
+        }
+        self.parent[y] = x;
+        self.parent[x] = g;
+        if g == NIL {
+            self.root = x;
+        } else if self.left[g] == y {
+            self.left[g] = x;
+        } else {
+            self.right[g] = x;
+        }
+    }
+    /// Insert x directly after the given segment (at the front for None).
+    fn insert_after(&mut self, x: usize, after: Option<usize>) {
+        self.left[x] = NIL;
+        self.right[x] = NIL;
+        if self.root == NIL {
+            self.root = x;
+            self.parent[x] = NIL;
+            return;
+        }
+        match after {
+            Some(a) if self.right[a] == NIL => {
+                self.right[a] = x;
+                self.parent[x] = a;
+            }
+            _ => {
+                let y = match after {
+                    Some(a) => self.leftmost(self.right[a]),
+                    None => self.leftmost(self.root),
+                };
+                self.left[y] = x;
+                self.parent[x] = y;
+            }
+        }
+        while self.parent[x] != NIL && self.prio[x] > self.prio[self.parent[x]] {
+            self.rotate_up(x);
+        }
+    }
+    fn remove(&mut self, x: usize) {
+        loop {
+            let (l, r) = (self.left[x], self.right[x]);
+            let child = if l == NIL {
+                r
+            } else if r == NIL || self.prio[l] > self.prio[r] {
+                l
+            } else {
+                r
+            };
+            if child == NIL {
+                break;
+            }
+            self.rotate_up(child);
+        }
+        let p = self.parent[x];
+        if p == NIL {
+            self.root = NIL;
+        } else if self.left[p] == x {
+            self.left[p] = NIL;
+        } else {
+            self.right[p] = NIL;
+        }
+        self.parent[x] = NIL;
+    }
+}
+
+/// Find all pairs of intersecting (or touching) segments as sorted index pairs with the
+/// Bentley-Ottmann sweep in O((n + k) log n) for k intersecting pairs. Segments which overlap
+/// each other are reported at every sweep point they share with a third segment, so many
+/// overlapping segments can make it slower. The sweep points are computed exactly, for `i64`
+/// this works for coordinates up to 2^40 in absolute value, for larger ones convert them to
+/// `i128` first.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let s = |x1: i64, y1, x2, y2| Segment::new(Point2D::new(x1, y1), Point2D::new(x2, y2));
+/// let segments = [s(0, 0, 4, 4), s(0, 4, 4, 0), s(5, 5, 6, 6), s(4, 4, 8, 4), s(7, 0, 7, 1)];
+/// assert!(segment_intersections(&segments) == vec![(0, 1), (0, 3)]);
+/// // three segments through one point, a vertical one touching another one and a single point
+/// let segments = [s(0, 0, 2, 2), s(0, 2, 2, 0), s(1, 0, 1, 3), s(1, 3, 3, 3), s(1, 1, 1, 1)];
+/// assert!(segment_intersections(&segments) == vec![(0, 1), (0, 2), (0, 4), (1, 2), (1, 4), (2, 3), (2, 4)]);
+/// ```
+pub fn segment_intersections<T: Num>(segments: &[Segment<T>]) -> Vec<(usize, usize)> {
+    // the endpoints of every segment ordered like the sweep points
+    let ends = segments
+        .iter()
+        .map(|s| {
+            let (a, b) = (s.a.widen(), s.b.widen());
+            if b < a {
+                (b, a)
+            } else {
+                (a, b)
+            }
+        })
+        .collect::<Vec<_>>();
+    let dir = |s: usize| ends[s].1.clone() - ends[s].0.clone();
+    let point = |p: &Point2D<T::Wide>| SweepPoint {
+        x: p.x.clone(),
+        y: p.y.clone(),
+        d: T::Wide::one(),
+    };
+    let mut queue = BTreeMap::new();
+    for (i, (a, b)) in ends.iter().enumerate() {
+        queue
+            .entry(point(a))
+            .or_insert_with(Event::default)
+            .starts
+            .push(i);
+        queue
+            .entry(point(b))
+            .or_insert_with(Event::default)
+            .ends
+            .push(i);
+    }
+    // Less if segment s passes below p, Equal if it contains p. Vertical segments in the status
+    // always contain the current sweep point.
+    let side = |s: usize, p: &Point2D<T::Wide>| {
+        let (a, b) = &ends[s];
+        if a.x == b.x {
+            return Ordering::Equal;
+        }
+        cmp(&T::Wide::zero(), &dir(s).cross(p.clone() - a.clone()))
+    };
+    let collinear = |s: usize, t: usize| {
+        let d = dir(s);
+        d.cross(dir(t)).is_zero() && d.cross(ends[t].0.clone() - ends[s].0.clone()).is_zero()
+    };
+    // the order of segments leaving the same point, from bottom to top
+    let by_direction =
+        |s: &usize, t: &usize| cmp(&T::Wide::zero(), &dir(*s).cross(dir(*t))).then(s.cmp(t));
+    let mut status = Status::new(segments.len());
+    let mut pairs = vec![];
+    while let Some((p, mut event)) = queue.pop_first() {
+        let endpoint = match (event.starts.first(), event.ends.first()) {
+            (Some(&s), _) => Some(&ends[s].0),
+            (None, Some(&s)) => Some(&ends[s].1),
+            _ => None,
+        };
+        // the segments in the status which contain p and the one directly below them
+        let mut block = vec![];
+        let below = if let Some(q) = endpoint {
+            let below = status.last_where(|s| side(s, q) == Ordering::Less);
+            let mut next = below.map_or(status.first(), |s| status.next(s));
+            while let Some(s) = next.filter(|&s| side(s, q) == Ordering::Equal) {
+                block.push(s);
+                next = status.next(s);
+            }
+            below
+        } else {
+            // all segments through p are neighbours, those which were not found to cross
+            // there overlap with a neighbour
+            event.crossing.sort_unstable();
+            let through =
+                |s: usize, t: usize| event.crossing.binary_search(&s).is_ok() || collinear(s, t);
+            let mut lo = event.crossing[0];
+            while let Some(s) = status.prev(lo).filter(|&s| through(s, lo)) {
+                lo = s;
+            }
+            block.push(lo);
+            while let Some(s) = status.next(block[block.len() - 1]) {
+                if !through(s, block[block.len() - 1]) {
+                    break;
+                }
+                block.push(s);
+            }
+            status.prev(lo)
+        };
+        let mut here = block.clone();
+        here.extend(event.starts.iter().copied());
+        for (k, &s) in here.iter().enumerate() {
+            for &t in &here[k + 1..] {
+                pairs.push((s.min(t), s.max(t)));
+            }
+        }
+        for &s in &block {
+            status.remove(s);
+        }
+        event.ends.sort_unstable();
+        here.retain(|s| event.ends.binary_search(s).is_err());
+        here.sort_by(by_direction);
+        let mut after = below;
+        for &s in &here {
+            status.insert_after(s, after);
+            after = Some(s);
+        }
+        let above = after.map_or(status.first(), |s| status.next(s));
+        let mut check = |s: Option<usize>, t: Option<usize>| {
+            let (Some(s), Some(t)) = (s, t) else {
+                return;
+            };
+            let (a, c) = (&segments[s], &segments[t]);
+            let Some((u, v, d)) = a.line().intersection_times(&c.line()) else {
+                return;
+            };
+            let zero = T::Wide::zero();
+            if u < zero || d < u || v < zero || d < v {
+                return;
+            }
+            let (a, ab) = (a.a.widen(), a.b.widen() - a.a.widen());
+            let q = SweepPoint {
+                x: a.x * d.clone() + ab.x * u.clone(),
+                y: a.y * d.clone() + ab.y * u,
+                d,
+            };
+            if q > p {
+                let event = queue.entry(q).or_insert_with(Event::default);
+                event.crossing.extend([s, t]);
+            }
+        };
+        match (here.first(), here.last()) {
+            (Some(&lo), Some(&hi)) => {
+                check(below, Some(lo));
+                check(Some(hi), above);
+            }
+            _ => check(below, above),
+        }
+    }
+    pairs.sort_unstable();
+    pairs.dedup();
+    pairs
+}
+
+/// Count the pairs of a horizontal and a vertical segment which share a point with a sweep line
+/// and a Fenwick tree in O(n log n). Segments consisting of a single point count as vertical,
+/// pairs of two horizontal or two vertical segments are not counted. Panics if a segment is
+/// neither horizontal nor vertical.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::geometry::*;
+///
+/// let s = |x1: i64, y1, x2, y2| Segment::new(Point2D::new(x1, y1), Point2D::new(x2, y2));
+/// // the horizontal segments on y = 3 overlap, both meet the last vertical one at (4, 3)
+/// let segments = [s(0, 1, 5, 1), s(5, 3, 0, 3), s(1, 0, 1, 5), s(4, 3, 4, 6), s(3, 3, 8, 3)];
+/// assert!(count_hv_intersections(&segments) == 4);
+/// ```
+pub fn count_hv_intersections<T: Num>(segments: &[Segment<T>]) -> usize {
+    // events (x, kind, y1, y2) with kind 0 = start of a horizontal, 1 = vertical, 2 = end
+    let mut events = vec![];
+    let mut ys = vec![];
+    for s in segments {
+        let (a, b) = if s.a <= s.b {
+            (&s.a, &s.b)
+        } else {
+            (&s.b, &s.a)
+        };
+        if a.x == b.x {
+            let (y1, y2) = if a.y < b.y {
+                (&a.y, &b.y)
+            } else {
+                (&b.y, &a.y)
+            };
+            events.push((&a.x, 1, y1, y2));
+        } else if a.y == b.y {
+            events.push((&a.x, 0, &a.y, &a.y));
+            events.push((&b.x, 2, &a.y, &a.y));
+            ys.push(a.y.clone());
+        } else {
+            panic!("the segment is neither horizontal nor vertical");
+        }
+    }
+    ys.sort_by(cmp);
+    ys.dedup();
+    events.sort_by(|a, b| cmp(a.0, b.0).then(a.1.cmp(&b.1)));
+    let mut fenwick = vec![0i64; ys.len() + 1];
+    let mut count = 0;
+    for (_, kind, y1, y2) in events {
+        // number of ys below y (or at most y if inclusive)
+        let rank = |y: &T, inclusive: bool| ys.partition_point(|v| v < y || (inclusive && v == y));
+        if kind == 1 {
+            let prefix = |mut i: usize| {
+                let mut sum = 0;
+                while i > 0 {
+                    sum += fenwick[i];
+                    i &= i - 1;
+                }
+                sum
+            };
+            count += (prefix(rank(y2, true)) - prefix(rank(y1, false))) as usize;
+        } else {
+            let d = if kind == 0 { 1 } else { -1 };
+            let mut i = rank(y1, false) + 1;
+            while i < fenwick.len() {
+                fenwick[i] += d;
+                i += i & i.wrapping_neg();
+            }
+        }
+    }
+    count
+}
@@ -1,7 +1,11 @@
+#[cfg(feature = "regex")]
 use regex::Regex;
 use std::{
-    fs::File,
+    env,
+    ffi::OsString,
+    fs::{self, File},
     io::{stdin, stdout, BufReader, BufWriter, Cursor, Read, Stdin, Stdout, Write},
+    path::{Path, PathBuf},
     str::from_utf8,
 };
 
@@ -137,7 +141,7 @@ impl<R: Read, W: Write> Io<R, W> {
         from_utf8(&buf)
             .expect("data was not valid UTF-8 and could not be converted to a String")
             .parse()
-            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "could not parse value"))
+            .map_err(|_| std::io::Error::other("could not parse value"))
             .unwrap()
     }
     /// This function reads the entire contents in the reader to a String to be used outside of the
@@ -340,9 +344,9 @@ impl<R: Read, W: Write> Io<R, W> {
     /// ```
     pub fn nums<T: std::str::FromStr<Err = impl std::fmt::Debug>>(&mut self) -> Vec<T> {
         let file = self.read_all();
-        let re = Regex::new(r"(-?\d+)").unwrap();
-        re.captures_iter(&file)
-            .map(|x| x.get(1).unwrap().as_str().parse::<T>().unwrap())
+        numbers(&file, true)
+            .into_iter()
+            .map(|x| x.parse::<T>().unwrap())
             .collect::<Vec<T>>()
     }
     /// This function reads the whole file and then returns all numbers matching the regex r'\d+'
@@ -358,9 +362,9 @@ impl<R: Read, W: Write> Io<R, W> {
     /// ```
     pub fn pnums<T: std::str::FromStr<Err = impl std::fmt::Debug>>(&mut self) -> Vec<T> {
         let file = self.read_all();
-        let re = Regex::new(r"(\d+)").unwrap();
-        re.captures_iter(&file)
-            .map(|x| x.get(1).unwrap().as_str().parse::<T>().unwrap())
+        numbers(&file, false)
+            .into_iter()
+            .map(|x| x.parse::<T>().unwrap())
             .collect::<Vec<T>>()
     }
     /// This function skips one string which would be read.
@@ -395,6 +399,41 @@ impl<R: Read, W: Write> Io<R, W> {
     }
 }
 
+/// Returns all matches of the regex r'-?\d+' (or r'\d+' if `signed` is false).
+#[cfg(feature = "regex")]
+fn numbers(text: &str, signed: bool) -> Vec<&str> {
+    let re = if signed {
+        Regex::new(r"-?\d+").unwrap()
+    } else {
+        Regex::new(r"\d+").unwrap()
+    };
+    re.find_iter(text).map(|m| m.as_str()).collect()
+}
+
+/// Returns all matches of the regex r'-?\d+' (or r'\d+' if `signed` is false) without the regex
+/// crate. Only ASCII digits are recognized.
+#[cfg(not(feature = "regex"))]
+fn numbers(text: &str, signed: bool) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut res = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if signed && bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        if bytes[i].is_ascii_digit() {
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            res.push(&text[start..i]);
+        } else {
+            i += 1;
+        }
+    }
+    res
+}
+
 impl Io<Stdin, Stdout> {
     /// This functions creates the default I/O handler using stdin and stdout as reader and writer.
     pub fn new() -> Io<Stdin, Stdout> {
@@ -427,6 +466,166 @@ impl Io<File, Stdout> {
             writer: BufWriter::new(stdout()),
         }
     }
+    /// This function opens your puzzle input for the given Advent of Code day. The file is
+    /// expected at `<root>/inputs/<year>/day<day>.txt` with the day padded to two digits, see
+    /// [`aoc_root`] for how the root directory is found. Nothing is downloaded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use crate::cp_rs::io::*;
+    /// // reads <root>/inputs/2023/day05.txt
+    /// let mut io = Io::aoc(2023, 5);
+    /// let seeds = io.nums::<u64>();
+    /// ```
+    pub fn aoc(year: u32, day: u32) -> Io<File, Stdout> {
+        Self::open_aoc_file(&aoc_input_path(year, day))
+    }
+    /// This function opens the n-th example of the given Advent of Code day. The file is expected
+    /// at `<root>/examples/<year>/day<day>_<n>.txt` with the day padded to two digits, see
+    /// [`aoc_root`] for how the root directory is found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use crate::cp_rs::io::*;
+    /// // reads <root>/examples/2023/day05_1.txt
+    /// let mut io = Io::aoc_example(2023, 5, 1);
+    /// let seeds = io.nums::<u64>();
+    /// ```
+    pub fn aoc_example(year: u32, day: u32, n: u32) -> Io<File, Stdout> {
+        Self::open_aoc_file(&aoc_example_path(year, day, n))
+    }
+    pub(crate) fn open_aoc_file(path: &Path) -> Io<File, Stdout> {
+        let file = File::open(path).unwrap_or_else(|err| {
+            panic!(
+                "could not open Advent of Code file {} ({}), the root directory can be set with \
+                 the {} environment variable or `{}` in [package.metadata.cp-rs] of your Cargo.toml",
+                path.display(),
+                err,
+                AOC_ROOT_ENV,
+                AOC_ROOT_KEY
+            )
+        });
+        Io {
+            reader: BufReader::new(file),
+            writer: BufWriter::new(stdout()),
+        }
+    }
+}
+
+/// Environment variable which overrides the Advent of Code root directory.
+pub const AOC_ROOT_ENV: &str = "CP_RS_AOC_ROOT";
+
+/// Key in the `[package.metadata.cp-rs]` table of Cargo.toml which sets the Advent of Code root
+/// directory (relative to the Cargo.toml).
+pub const AOC_ROOT_KEY: &str = "aoc-root";
+
+/// This function returns the directory containing the `inputs` and `examples` folders for
+/// Advent of Code. It is resolved in the following order:
+///
+/// 1. the environment variable `CP_RS_AOC_ROOT`
+/// 2. `aoc-root = "..."` in the `[package.metadata.cp-rs]` table of the Cargo.toml found in
+///    `CARGO_MANIFEST_DIR` (set by `cargo run` and `cargo test`)
+/// 3. the directory of that Cargo.toml
+/// 4. the current working directory
+///
+/// # Example
+///
+/// ```toml
+/// [package.metadata.cp-rs]
+/// aoc-root = "aoc"
+/// ```
+pub fn aoc_root() -> PathBuf {
+    resolve_aoc_root(
+        env::var_os(AOC_ROOT_ENV),
+        env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from),
+    )
+}
+
+/// Resolves the root directory like [`aoc_root`], but from the given value of the environment
+/// variable and manifest directory.
+pub(crate) fn resolve_aoc_root(
+    env_root: Option<OsString>,
+    manifest_dir: Option<PathBuf>,
+) -> PathBuf {
+    if let Some(root) = env_root {
+        return PathBuf::from(root);
+    }
+    match manifest_dir {
+        Some(dir) => match fs::read_to_string(dir.join("Cargo.toml")) {
+            Ok(manifest) => match manifest_aoc_root(&manifest) {
+                Some(root) => dir.join(root),
+                None => dir,
+            },
+            Err(_) => dir,
+        },
+        None => PathBuf::from("."),
+    }
+}
+
+/// This function returns the path of the puzzle input for the given day:
+/// `<root>/inputs/<year>/day<day>.txt`.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::io::*;
+/// let path = aoc_input_path(2023, 5);
+/// assert!(path.ends_with("inputs/2023/day05.txt"));
+/// ```
+pub fn aoc_input_path(year: u32, day: u32) -> PathBuf {
+    aoc_input_path_in(&aoc_root(), year, day)
+}
+
+/// This function returns the path of the n-th example for the given day:
+/// `<root>/examples/<year>/day<day>_<n>.txt`.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::io::*;
+/// let path = aoc_example_path(2023, 5, 1);
+/// assert!(path.ends_with("examples/2023/day05_1.txt"));
+/// ```
+pub fn aoc_example_path(year: u32, day: u32, n: u32) -> PathBuf {
+    aoc_example_path_in(&aoc_root(), year, day, n)
+}
+
+pub(crate) fn aoc_input_path_in(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join("inputs")
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+pub(crate) fn aoc_example_path_in(root: &Path, year: u32, day: u32, n: u32) -> PathBuf {
+    root.join("examples")
+        .join(year.to_string())
+        .join(format!("day{:02}_{}.txt", day, n))
+}
+
+/// Looks up `aoc-root` in the `[package.metadata.cp-rs]` table. This only understands the simple
+/// `key = "value"` form, which is all we need.
+fn manifest_aoc_root(manifest: &str) -> Option<String> {
+    let mut in_table = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_table = line == "[package.metadata.cp-rs]";
+            continue;
+        }
+        if !in_table {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == AOC_ROOT_KEY {
+                let value = value.trim();
+                let value = value.split_once('#').map_or(value, |(v, _)| v).trim();
+                return Some(value.trim_matches(|c| c == '"' || c == '\'').to_string());
+            }
+        }
+    }
+    None
 }
 
 impl Io<File, File> {
@@ -441,12 +640,14 @@ impl Io<File, File> {
             .read(true)
             .write(true)
             .create(true)
+            .truncate(false)
             .open(filename_in)
             .unwrap();
         let writer = File::options()
             .read(true)
             .write(true)
             .create(true)
+            .truncate(false)
             .open(filename_out)
             .unwrap();
         Io {
@@ -464,6 +665,7 @@ impl Io<Stdin, File> {
                 .read(true)
                 .write(true)
                 .create(true)
+                .truncate(false)
                 .open(filename)
                 .unwrap(),
         );
@@ -492,6 +694,25 @@ impl Io<&[u8], Stdout> {
     }
 }
 
+impl<R: Read> Io<R, Vec<u8>> {
+    /// This function flushes and returns everything written so far, e.g. to check answers which
+    /// are written instead of returned.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::cp_rs::io::*;
+    /// let mut io = Io::with_reader_and_writer("".as_bytes(), vec![]);
+    /// io.writeln(42);
+    /// assert_eq!(io.take_output(), "42\n");
+    /// assert_eq!(io.take_output(), "");
+    /// ```
+    pub fn take_output(&mut self) -> String {
+        self.flush();
+        String::from_utf8(std::mem::take(self.writer.get_mut()))
+            .expect("output was not valid UTF-8")
+    }
+}
+
 /// Trait automatically implemented for Io struct which allows to get tuples with only one function
 /// call.
 pub trait Tuple<T> {
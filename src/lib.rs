@@ -12,11 +12,22 @@
 pub mod io;
 
 /// This module contains all sorts of utilities which can be helpful for competitive programming.
+/// The re-exported crates are only available with the corresponding cargo features (`radix_fmt`,
+/// `md5`, `cached` and `rug`).
 pub mod utils;
 
 /// This module contains geometry related structs (Point2D, Segment, ...) and methods for them
 pub mod geometry;
 
+/// This module contains the numeric traits (Scalar, Num) used by the geometry module, implemented
+/// for all primitive number types and for the rug types
+pub mod num;
+
+/// This module contains a runner for Advent of Code solutions which times them and checks their
+/// answers (requires the `aoc` feature)
+#[cfg(feature = "aoc")]
+pub mod aoc;
+
 #[cfg(test)]
 mod tests {
     mod io {
@@ -29,5 +40,111 @@ mod tests {
             let a: usize = io.read();
             io.write(a);
         }
+
+        #[test]
+        fn numbers() {
+            use crate::io::*;
+            let s = "5-3 a--7 -x 12\n-0";
+            assert_eq!(Io::from_str(s).nums::<i32>(), vec![5, -3, -7, 12, 0]);
+            assert_eq!(Io::from_str(s).pnums::<u32>(), vec![5, 3, 7, 12, 0]);
+        }
+
+        #[test]
+        fn aoc_files() {
+            use crate::io::*;
+            let root = std::env::temp_dir().join(format!("cp-rs-aoc-test-{}", std::process::id()));
+            std::fs::create_dir_all(root.join("inputs/2023")).unwrap();
+            std::fs::create_dir_all(root.join("examples/2023")).unwrap();
+            std::fs::write(root.join("inputs/2023/day05.txt"), "1 2 3").unwrap();
+            std::fs::write(root.join("examples/2023/day05_2.txt"), "4 5").unwrap();
+            let input = aoc_input_path_in(&root, 2023, 5);
+            let example = aoc_example_path_in(&root, 2023, 5, 2);
+            assert_eq!(Io::open_aoc_file(&input).nums::<usize>(), vec![1, 2, 3]);
+            assert_eq!(Io::open_aoc_file(&example).nums::<usize>(), vec![4, 5]);
+            let missing = aoc_input_path_in(&root, 2023, 6);
+            assert!(std::panic::catch_unwind(|| Io::open_aoc_file(&missing)).is_err());
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+
+        #[test]
+        fn aoc_root_resolution() {
+            use crate::io::*;
+            let dir = std::env::temp_dir().join(format!("cp-rs-aoc-root-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let env = Some("from-env".into());
+            assert_eq!(
+                resolve_aoc_root(env, Some(dir.clone())),
+                std::path::PathBuf::from("from-env")
+            );
+            assert_eq!(resolve_aoc_root(None, Some(dir.clone())), dir);
+            std::fs::write(
+                dir.join("Cargo.toml"),
+                "[package]\nname = \"x\"\n\n[package.metadata.cp-rs]\naoc-root = \"aoc\" # inputs\n",
+            )
+            .unwrap();
+            assert_eq!(resolve_aoc_root(None, Some(dir.clone())), dir.join("aoc"));
+            assert_eq!(resolve_aoc_root(None, None), std::path::PathBuf::from("."));
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+
+    #[cfg(feature = "aoc")]
+    mod aoc {
+        #[test]
+        fn runner() {
+            use crate::aoc::*;
+            let root =
+                std::env::temp_dir().join(format!("cp-rs-runner-test-{}", std::process::id()));
+            std::fs::create_dir_all(root.join("inputs/2022")).unwrap();
+            std::fs::create_dir_all(root.join("answers")).unwrap();
+            std::fs::write(root.join("inputs/2022/day01.txt"), "1\n2\n3").unwrap();
+            std::fs::write(root.join("inputs/2022/day02.txt"), "").unwrap();
+            std::fs::write(
+                root.join("answers/2022.txt"),
+                "# day part answer\n1 1 6\n1 2 7\n",
+            )
+            .unwrap();
+            let runner = Runner::new(2022)
+                .root(&root)
+                .day(
+                    1,
+                    |io: &mut Input| io.nums::<u32>().iter().sum::<u32>(),
+                    |io: &mut Input| io.nums::<u32>().iter().product::<u32>(),
+                )
+                .part(2, 1, |_: &mut Input| "unused")
+                .part(2, 2, |io: &mut Input| {
+                    io.writeln("written");
+                    ""
+                });
+            let outcomes = runner.run_days(&[1]);
+            assert_eq!(outcomes.len(), 2);
+            assert_eq!(outcomes[0].answer, "6");
+            assert!(outcomes[0].is_correct());
+            assert_eq!(outcomes[1].expected.as_deref(), Some("7"));
+            assert!(!outcomes[1].is_correct());
+            assert_eq!(runner.run_days(&[2])[1].answer, "written");
+            assert_eq!(day_from_name("day05"), 5);
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+
+        mod day01 {
+            use crate::aoc::Input;
+            pub fn part1(io: &mut Input) -> u32 {
+                io.nums::<u32>().iter().sum()
+            }
+            pub fn part2(io: &mut Input) -> String {
+                io.lines().join("|")
+            }
+            pub fn part3(io: &mut Input) {
+                let n = io.nums::<u32>().len();
+                io.writeln(n);
+            }
+        }
+
+        crate::aoc_test!(day01, part1, "1\n2\n3", 6);
+        crate::aoc_test!(day01, part2, "\na\nb", "a|b");
+        crate::aoc_test!(day01, part2, "c", "c", single_line);
+        crate::aoc_test!(day01, part3, "\n4 5\n6", writes 3);
+        crate::aoc_test!(day01, part3, "7", writes "1", single);
     }
 }
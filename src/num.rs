@@ -0,0 +1,372 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// Base trait for all values which can be used as coordinates. It is implemented for all
+/// primitive number types and (with the `rug` feature) for `Integer`, `Rational` and `Float`.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::num::*;
+///
+/// assert!(i64::zero() == 0);
+/// assert!(f32::one() == 1.0);
+/// assert!(0usize.is_zero());
+/// ```
+pub trait Scalar: Clone + PartialEq + PartialOrd + Debug {
+    /// The additive identity.
+    fn zero() -> Self;
+    /// The multiplicative identity.
+    fn one() -> Self;
+    /// Returns true if the value equals zero.
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+/// Numbers with the four basic operations. The operations take references, so they also work
+/// for the (non-Copy) rug types without cloning.
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::num::*;
+///
+/// fn sum_abs<T: Num>(v: &[T]) -> T {
+///     v.iter().fold(T::zero(), |acc, x| acc + x.abs())
+/// }
+///
+/// assert!(sum_abs(&[-1i64, 2, -3]) == 6);
+/// assert!(sum_abs(&[-1.5, 2.5]) == 4.0);
+/// // the inherent methods of the primitives shadow the trait methods
+/// assert!(Num::checked_add(&255u8, &1).is_none());
+/// assert!(Num::signum(&-3i32) == -1);
+/// assert!(Num::signum(&0.0) == 0.0);
+/// ```
+pub trait Num:
+    Scalar + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    /// The absolute value (the identity for unsigned types).
+    fn abs(&self) -> Self;
+    /// Returns -1, 0 or 1 depending on the sign (0 for zero, also for floats).
+    fn signum(&self) -> Self;
+    /// Addition which returns None on overflow (or a non-finite result for floats).
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    /// Subtraction which returns None on overflow (or a non-finite result for floats).
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+    /// Multiplication which returns None on overflow (or a non-finite result for floats).
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    /// Division which returns None when dividing by zero or on overflow.
+    fn checked_div(&self, rhs: &Self) -> Option<Self>;
+    /// Converts the value into a float, possibly losing precision.
+    fn to_f64(&self) -> f64;
+    /// A signed type which can hold sums of a few products of two differences of Self without
+    /// overflow, e.g. cross products: `i64` for 8 and 16 bit types and `i128` for 32 bit types.
+    /// The 64 bit types also use `i128`, which only suffices for values below 2^62 in absolute
+    /// value. Types without a bigger type (`i128`, floats and the rug types) use themselves,
+    /// `u128` values above `i128::MAX` wrap around.
+    type Wide: Num;
+    /// Converts the value into the wide type.
+    fn widen(&self) -> Self::Wide;
+    /// Converts the value into an exact rational number. Panics for NaN and infinite floats.
+    #[cfg(feature = "rug")]
+    fn to_rational(&self) -> rug::Rational;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            fn zero() -> Self {
+                0
+            }
+            fn one() -> Self {
+                1
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_checked {
+    () => {
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            Self::checked_add(*self, *rhs)
+        }
+        fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+            Self::checked_sub(*self, *rhs)
+        }
+        fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+            Self::checked_mul(*self, *rhs)
+        }
+        fn checked_div(&self, rhs: &Self) -> Option<Self> {
+            Self::checked_div(*self, *rhs)
+        }
+        fn to_f64(&self) -> f64 {
+            *self as f64
+        }
+        #[cfg(feature = "rug")]
+        fn to_rational(&self) -> rug::Rational {
+            rug::Rational::from(*self)
+        }
+    };
+}
+
+macro_rules! impl_signed {
+    ($($t:ty => $w:ty),*) => {$(
+        impl_int!($t);
+
+        impl Num for $t {
+            type Wide = $w;
+            fn widen(&self) -> $w {
+                *self as $w
+            }
+            fn abs(&self) -> Self {
+                <$t>::abs(*self)
+            }
+            fn signum(&self) -> Self {
+                <$t>::signum(*self)
+            }
+            impl_checked!();
+        }
+    )*};
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty => $w:ty),*) => {$(
+        impl_int!($t);
+
+        impl Num for $t {
+            type Wide = $w;
+            fn widen(&self) -> $w {
+                *self as $w
+            }
+            fn abs(&self) -> Self {
+                *self
+            }
+            fn signum(&self) -> Self {
+                (*self > 0) as $t
+            }
+            impl_checked!();
+        }
+    )*};
+}
+
+impl_signed!(i8 => i64, i16 => i64, i32 => i128, i64 => i128, i128 => i128, isize => i128);
+impl_unsigned!(u8 => i64, u16 => i64, u32 => i128, u64 => i128, u128 => i128, usize => i128);
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            fn zero() -> Self {
+                0.0
+            }
+            fn one() -> Self {
+                1.0
+            }
+        }
+
+        impl Num for $t {
+            type Wide = f64;
+            fn widen(&self) -> f64 {
+                *self as f64
+            }
+            fn abs(&self) -> Self {
+                <$t>::abs(*self)
+            }
+            fn signum(&self) -> Self {
+                if *self == 0.0 {
+                    0.0
+                } else {
+                    <$t>::signum(*self)
+                }
+            }
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                Some(self + rhs).filter(|r| r.is_finite())
+            }
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                Some(self - rhs).filter(|r| r.is_finite())
+            }
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                Some(self * rhs).filter(|r| r.is_finite())
+            }
+            fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                Some(self / rhs).filter(|r| r.is_finite())
+            }
+            fn to_f64(&self) -> f64 {
+                *self as f64
+            }
+            #[cfg(feature = "rug")]
+            fn to_rational(&self) -> rug::Rational {
+                rug::Rational::from_f64(*self as f64).expect("cannot convert NaN or infinity")
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+#[cfg(feature = "rug")]
+mod rug_impls {
+    use super::{Num, Scalar};
+    use rug::{Float, Integer, Rational};
+    use std::cmp::Ordering;
+
+    /// Precision of the `Float`s created by `zero` and `one`.
+    const FLOAT_PREC: u32 = 53;
+
+    impl Scalar for Integer {
+        fn zero() -> Self {
+            Integer::new()
+        }
+        fn one() -> Self {
+            Integer::from(1)
+        }
+    }
+
+    impl Num for Integer {
+        type Wide = Integer;
+        fn widen(&self) -> Self {
+            self.clone()
+        }
+        fn abs(&self) -> Self {
+            self.clone().abs()
+        }
+        fn signum(&self) -> Self {
+            Integer::from(self.cmp0() as i32)
+        }
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            Some(Integer::from(self + rhs))
+        }
+        fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+            Some(Integer::from(self - rhs))
+        }
+        fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+            Some(Integer::from(self * rhs))
+        }
+        fn checked_div(&self, rhs: &Self) -> Option<Self> {
+            (rhs.cmp0() != Ordering::Equal).then(|| Integer::from(self / rhs))
+        }
+        fn to_f64(&self) -> f64 {
+            Integer::to_f64(self)
+        }
+        fn to_rational(&self) -> Rational {
+            Rational::from(self.clone())
+        }
+    }
+
+    impl Scalar for Rational {
+        fn zero() -> Self {
+            Rational::new()
+        }
+        fn one() -> Self {
+            Rational::from(1)
+        }
+    }
+
+    impl Num for Rational {
+        type Wide = Rational;
+        fn widen(&self) -> Self {
+            self.clone()
+        }
+        fn abs(&self) -> Self {
+            self.clone().abs()
+        }
+        fn signum(&self) -> Self {
+            Rational::from(self.cmp0() as i32)
+        }
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            Some(Rational::from(self + rhs))
+        }
+        fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+            Some(Rational::from(self - rhs))
+        }
+        fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+            Some(Rational::from(self * rhs))
+        }
+        fn checked_div(&self, rhs: &Self) -> Option<Self> {
+            (rhs.cmp0() != Ordering::Equal).then(|| Rational::from(self / rhs))
+        }
+        fn to_f64(&self) -> f64 {
+            Rational::to_f64(self)
+        }
+        fn to_rational(&self) -> Rational {
+            self.clone()
+        }
+    }
+
+    impl Scalar for Float {
+        fn zero() -> Self {
+            Float::new(FLOAT_PREC)
+        }
+        fn one() -> Self {
+            Float::with_val(FLOAT_PREC, 1)
+        }
+    }
+
+    impl Num for Float {
+        type Wide = Float;
+        fn widen(&self) -> Self {
+            self.clone()
+        }
+        fn abs(&self) -> Self {
+            self.clone().abs()
+        }
+        fn signum(&self) -> Self {
+            match self.cmp0() {
+                Some(ord) => Float::with_val(self.prec(), ord as i32),
+                None => self.clone(),
+            }
+        }
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            Some(Float::with_val(self.prec().max(rhs.prec()), self + rhs)).filter(Float::is_finite)
+        }
+        fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+            Some(Float::with_val(self.prec().max(rhs.prec()), self - rhs)).filter(Float::is_finite)
+        }
+        fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+            Some(Float::with_val(self.prec().max(rhs.prec()), self * rhs)).filter(Float::is_finite)
+        }
+        fn checked_div(&self, rhs: &Self) -> Option<Self> {
+            Some(Float::with_val(self.prec().max(rhs.prec()), self / rhs)).filter(Float::is_finite)
+        }
+        fn to_f64(&self) -> f64 {
+            Float::to_f64(self)
+        }
+        fn to_rational(&self) -> Rational {
+            Float::to_rational(self).expect("cannot convert NaN or infinity")
+        }
+    }
+}
+
+/// Computes the (non-negative) greatest common divisor of two integers with Euclid's algorithm.
+/// `T::MIN` is fine as input, but panics if the result is `-T::MIN`, which doesn't fit into T
+/// (i.e. for `gcd(T::MIN, 0)` and `gcd(T::MIN, T::MIN)`).
+///
+/// # Example
+///
+/// ```
+/// use crate::cp_rs::num::*;
+///
+/// assert!(gcd(12i64, -18) == 6);
+/// assert!(gcd(0u32, 5) == 5);
+/// assert!(gcd(0, 0) == 0);
+/// assert!(gcd(i64::MIN, -1) == 1 && gcd(i64::MIN, 6) == 2);
+/// assert!(std::panic::catch_unwind(|| gcd(i64::MIN, 0)).is_err());
+/// ```
+pub fn gcd<T: Num>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while !b.is_zero() {
+        // the division only overflows for T::MIN / -1, where the remainder is zero
+        let r = match a.checked_div(&b) {
+            Some(q) => a - q * b.clone(),
+            None => T::zero(),
+        };
+        a = b;
+        b = r;
+    }
+    if a < T::zero() {
+        a = T::zero()
+            .checked_sub(&a)
+            .expect("the gcd does not fit into the type");
+    }
+    a
+}
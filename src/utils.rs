@@ -10,9 +10,11 @@
 /// let n: usize = 10;
 /// assert!(radix(n, 2).to_string() == "1010");
 /// ```
+#[cfg(feature = "radix_fmt")]
 pub use radix_fmt::radix;
 
 #[doc(inline)]
+#[cfg(feature = "radix_fmt")]
 pub use radix_fmt::Radix;
 
 #[doc(inline)]
@@ -26,6 +28,7 @@ pub use radix_fmt::Radix;
 /// let hash = md5::compute("pqrstuv1048970");
 /// assert!(format!("{:?}", hash) == "000006136ef2ff3b291c85725f17325c");
 /// ```
+#[cfg(feature = "md5")]
 pub use md5;
 
 #[doc(inline)]
@@ -43,6 +46,7 @@ pub use md5;
 ///
 /// assert!(fib(5) == 8)
 /// ```
+#[cfg(feature = "cached")]
 pub use cached::proc_macro::cached;
 
 #[doc(inline)]
@@ -58,6 +62,7 @@ pub use cached::proc_macro::cached;
 /// assert!(*c.real() == 1.0);
 /// assert!(*c.imag() == 2.0);
 /// ```
+#[cfg(feature = "rug")]
 pub use rug::Complex;
 
 #[doc(inline)]
@@ -72,6 +77,7 @@ pub use rug::Complex;
 /// num *= 10;
 /// assert!(num % 10 == 0);
 /// ```
+#[cfg(feature = "rug")]
 pub use rug::Integer;
 
 #[doc(inline)]
@@ -86,6 +92,7 @@ pub use rug::Integer;
 /// let sqrt  = float.sqrt();
 /// assert!(sqrt == 2.0);
 /// ```
+#[cfg(feature = "rug")]
 pub use rug::Float;
 
 #[doc(inline)]
@@ -100,4 +107,5 @@ pub use rug::Float;
 /// rational *= 3;
 /// assert!(rational == -2);
 /// ```
+#[cfg(feature = "rug")]
 pub use rug::Rational;